@@ -61,6 +61,26 @@ class AccessPassController {
     }
   }
 
+  /**
+   * Get a single access pass
+   * GET /v1/access-passes/:id
+   */
+  async getAccessPass(
+    req: AuthenticatedRequest,
+    res: Response,
+    next: NextFunction
+  ): Promise<void> {
+    try {
+      const { id } = req.params;
+
+      const result = await accessPassService.getAccessPass(req.account!.id, id);
+
+      sendSuccess(res, result);
+    } catch (error) {
+      next(error);
+    }
+  }
+
   /**
    * Update an access pass
    * PATCH /v1/access-passes/:id
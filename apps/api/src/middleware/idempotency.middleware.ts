@@ -0,0 +1,149 @@
+import crypto from 'crypto';
+import { Response, NextFunction } from 'express';
+import { AuthenticatedRequest } from '../types';
+import { sendError } from '../utils/response';
+import redis from '../config/redis';
+import logger from '../config/logger';
+
+export const IDEMPOTENCY_TTL_SECONDS = 24 * 60 * 60; // 24 hours
+// Claims of requests still running expire sooner, so a crashed request frees its key
+export const PENDING_TTL_SECONDS = 60;
+const MAX_KEY_LENGTH = 255;
+
+interface StoredRequest {
+  fingerprint: string;
+  statusCode?: number;
+  body?: unknown;
+}
+
+/**
+ * Idempotency-Key Middleware
+ * Makes POST requests safe to retry. The first request with a key is processed
+ * and its response stored for 24 hours; a repeat of the same request with that
+ * key gets the stored response instead of performing the operation again.
+ * - Keys are scoped to the authenticated account
+ * - Reusing a key for a different request is rejected (422)
+ * - Repeating a key while the first request is still running is rejected (409);
+ *   the claim expires after 60 seconds if that request never responds
+ * - 5xx responses are not stored, so the request can be retried
+ * - Requests are rejected (503) while the idempotency store is unavailable
+ * Must run after authentication.
+ */
+export async function idempotency(
+  req: AuthenticatedRequest,
+  res: Response,
+  next: NextFunction
+): Promise<void> {
+  const key = req.headers['idempotency-key'];
+
+  if (req.method !== 'POST' || typeof key !== 'string' || !req.account) {
+    next();
+    return;
+  }
+
+  if (key.length === 0 || key.length > MAX_KEY_LENGTH) {
+    sendError(
+      res,
+      'VALIDATION_ERROR',
+      `Idempotency-Key must be between 1 and ${MAX_KEY_LENGTH} characters`,
+      400
+    );
+    return;
+  }
+
+  const storageKey = `idempotency:${req.account.id}:${key}`;
+  const fingerprint = crypto
+    .createHash('sha256')
+    .update(`${req.method} ${req.originalUrl} ${JSON.stringify(req.body ?? {})}`)
+    .digest('hex');
+
+  try {
+    const previous = await claim(storageKey, fingerprint);
+
+    if (previous && previous.fingerprint !== fingerprint) {
+      sendError(
+        res,
+        'IDEMPOTENCY_KEY_REUSED',
+        'Idempotency key was already used for a different request',
+        422
+      );
+      return;
+    }
+
+    if (previous && previous.statusCode === undefined) {
+      sendError(
+        res,
+        'IDEMPOTENCY_KEY_IN_USE',
+        'A request with this idempotency key is still being processed',
+        409
+      );
+      return;
+    }
+
+    if (previous) {
+      res.setHeader('Idempotent-Replayed', 'true');
+      res.status(previous.statusCode!).json(previous.body);
+      return;
+    }
+  } catch (error) {
+    // Processing without the store could perform the operation twice
+    logger.error({ error, idempotencyKey: key }, 'Idempotency store unavailable');
+    sendError(
+      res,
+      'SERVICE_UNAVAILABLE',
+      'Idempotency store unavailable, please retry the request',
+      503
+    );
+    return;
+  }
+
+  // Store the response once the handler sends it
+  const json = res.json.bind(res);
+  res.json = (body: unknown) => {
+    const stored =
+      res.statusCode < 500
+        ? redis.set(
+            storageKey,
+            JSON.stringify({ fingerprint, statusCode: res.statusCode, body }),
+            'EX',
+            IDEMPOTENCY_TTL_SECONDS
+          )
+        : redis.del(storageKey);
+    stored.catch((error) => {
+      logger.error({ error, idempotencyKey: key }, 'Failed to store idempotent response');
+    });
+    return json(body);
+  };
+
+  next();
+}
+
+/**
+ * Claim a key for a new request
+ * Returns null once the key is claimed, or the request stored under it. A key
+ * that expires between the claim attempt and the lookup is claimed again.
+ */
+async function claim(storageKey: string, fingerprint: string): Promise<StoredRequest | null> {
+  const pending: StoredRequest = { fingerprint };
+
+  for (let attempt = 0; attempt < 2; attempt++) {
+    const claimed = await redis.set(
+      storageKey,
+      JSON.stringify(pending),
+      'EX',
+      PENDING_TTL_SECONDS,
+      'NX'
+    );
+    if (claimed) {
+      return null;
+    }
+
+    const stored = await redis.get(storageKey);
+    if (stored) {
+      return JSON.parse(stored);
+    }
+  }
+
+  // The key keeps changing hands; treat it as in use
+  return { fingerprint };
+}
@@ -2,12 +2,14 @@ import { Router } from 'express';
 import { accessPassController } from '../controllers/access-pass.controller';
 import { authenticateRequest } from '../middleware/auth.middleware';
 import { apiRateLimiter } from '../middleware/rate-limit.middleware';
+import { idempotency } from '../middleware/idempotency.middleware';
 
 const router = Router();
 
 // All routes require authentication
 router.use(authenticateRequest);
 router.use(apiRateLimiter);
+router.use(idempotency);
 
 /**
  * @route   POST /v1/access-passes
@@ -23,6 +25,13 @@ router.post('/', accessPassController.issueAccessPass.bind(accessPassController)
  */
 router.get('/', accessPassController.listAccessPasses.bind(accessPassController));
 
+/**
+ * @route   GET /v1/access-passes/:id
+ * @desc    Get a single access pass
+ * @access  Private (Requires authentication)
+ */
+router.get('/:id', accessPassController.getAccessPass.bind(accessPassController));
+
 /**
  * @route   PATCH /v1/access-passes/:id
  * @desc    Update an access pass
@@ -2,6 +2,7 @@ import { Router } from 'express';
 import { cardTemplateController } from '../controllers/card-template.controller';
 import { authenticateRequest, requireEnterprise } from '../middleware/auth.middleware';
 import { apiRateLimiter } from '../middleware/rate-limit.middleware';
+import { idempotency } from '../middleware/idempotency.middleware';
 
 const router = Router();
 
@@ -9,6 +10,7 @@ const router = Router();
 router.use(authenticateRequest);
 router.use(requireEnterprise);
 router.use(apiRateLimiter);
+router.use(idempotency);
 
 /**
  * @route   POST /v1/console/card-templates
@@ -213,6 +213,53 @@ class AccessPassService {
     }
   }
 
+  /**
+   * Get a single access pass
+   */
+  async getAccessPass(accountId: string, passId: string) {
+    try {
+      const accessPass = await prisma.accessPass.findFirst({
+        where: {
+          exId: passId,
+          cardTemplate: {
+            accountId,
+          },
+        },
+        include: {
+          cardTemplate: true,
+        },
+      });
+
+      if (!accessPass) {
+        throw new AppError('ACCESS_PASS_NOT_FOUND', 'Access pass not found', 404);
+      }
+
+      return {
+        id: accessPass.exId,
+        card_template_id: accessPass.cardTemplate.exId,
+        employee_id: accessPass.employeeId,
+        full_name: accessPass.fullName,
+        email: accessPass.email,
+        phone_number: accessPass.phoneNumber,
+        classification: accessPass.classification,
+        title: accessPass.title,
+        tag_id: accessPass.tagId,
+        site_code: accessPass.siteCode,
+        card_number: accessPass.cardNumber,
+        start_date: accessPass.startDate,
+        expiration_date: accessPass.expirationDate,
+        state: accessPass.state,
+        install_url: accessPass.installUrl,
+        metadata: accessPass.metadata,
+        created_at: accessPass.createdAt,
+        updated_at: accessPass.updatedAt,
+      };
+    } catch (error) {
+      logger.error({ error }, 'Failed to get access pass');
+      throw error;
+    }
+  }
+
   /**
    * Update an access pass
    */
@@ -0,0 +1,38 @@
+import fs from 'fs';
+import path from 'path';
+import { encodePayload, generatePayloadSignature, verifyPayloadSignature } from '../auth';
+
+/**
+ * Cross-language signature vectors shared with the Rust SDK
+ * (packages/rust-sdk/tests/fixtures/signature_vectors.json).
+ *
+ * Each vector's `canonical` body is what the SDK sends. Express parses it and
+ * the auth middleware re-encodes it with JSON.stringify, so the re-encoded
+ * payload must be byte-identical for the SDK's signature to verify.
+ */
+interface SignatureVector {
+  name: string;
+  input: string;
+  canonical: string;
+  sig_payload: string;
+  signature: string;
+}
+
+const vectorsPath = path.resolve(
+  __dirname,
+  '../../../../../packages/rust-sdk/tests/fixtures/signature_vectors.json'
+);
+const { shared_secret: sharedSecret, vectors } = JSON.parse(
+  fs.readFileSync(vectorsPath, 'utf-8')
+) as { shared_secret: string; vectors: SignatureVector[] };
+
+describe('SDK signature vectors', () => {
+  it.each(vectors.map((v) => [v.name, v] as const))('%s', (_name, vector) => {
+    const parsedBody = JSON.parse(vector.canonical);
+
+    expect(JSON.stringify(parsedBody)).toBe(vector.canonical);
+    expect(encodePayload(parsedBody)).toBe(vector.sig_payload);
+    expect(generatePayloadSignature(sharedSecret, vector.sig_payload)).toBe(vector.signature);
+    expect(verifyPayloadSignature(sharedSecret, vector.sig_payload, vector.signature)).toBe(true);
+  });
+});
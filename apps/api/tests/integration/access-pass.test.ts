@@ -1,6 +1,12 @@
-import { describe, it, expect, beforeAll, afterAll } from 'vitest';
+import { describe, it, expect, beforeAll, afterAll, vi } from 'vitest';
+import crypto from 'crypto';
 import request from 'supertest';
 import createApp from '../../src/app';
+import redis from '../../src/config/redis';
+import {
+  IDEMPOTENCY_TTL_SECONDS,
+  PENDING_TTL_SECONDS,
+} from '../../src/middleware/idempotency.middleware';
 import {
   createTestAccount,
   generateAuthHeaders,
@@ -148,6 +154,110 @@ describe('Access Pass API Integration Tests', () => {
     });
   });
 
+  describe('POST /v1/access-passes - Idempotency-Key', () => {
+    const issuePayload = (fullName: string) => ({
+      card_template_id: testCardTemplate.exId,
+      full_name: fullName,
+      start_date: new Date('2025-01-01T00:00:00Z').toISOString(),
+      expiration_date: new Date('2026-01-01T00:00:00Z').toISOString(),
+      site_code: '100',
+      card_number: '12345',
+    });
+
+    const issue = (payload: any, idempotencyKey: string) =>
+      request(app)
+        .post('/v1/access-passes')
+        .set(generateAuthHeaders(testAccount.accountId, testAccount.sharedSecret, payload))
+        .set('Idempotency-Key', idempotencyKey)
+        .send(payload);
+
+    it('should return the original access pass when a key is replayed', async () => {
+      const idempotencyKey = `issue-${Date.now()}`;
+      const payload = issuePayload('Idempotent User');
+
+      const first = await issue(payload, idempotencyKey).expect(201);
+      const replayed = await issue(payload, idempotencyKey).expect(201);
+
+      expect(replayed.headers['idempotent-replayed']).toBe('true');
+      expect(replayed.body.data.id).toBe(first.body.data.id);
+      expect(first.headers['idempotent-replayed']).toBeUndefined();
+    });
+
+    it('should reject a key reused for a different request', async () => {
+      const idempotencyKey = `issue-reused-${Date.now()}`;
+
+      await issue(issuePayload('First User'), idempotencyKey).expect(201);
+      const response = await issue(issuePayload('Second User'), idempotencyKey)
+        .expect('Content-Type', /json/)
+        .expect(422);
+
+      expect(response.body).toHaveProperty('success', false);
+      expect(response.body.error).toHaveProperty('code', 'IDEMPOTENCY_KEY_REUSED');
+    });
+
+    it('should free a key whose request never finished once its claim expires', async () => {
+      const idempotencyKey = `issue-crashed-${Date.now()}`;
+      const storageKey = `idempotency:${testAccount.account.id}:${idempotencyKey}`;
+      const payload = issuePayload('Crashed Request User');
+
+      // A claim left behind by the same request, which crashed before responding
+      const fingerprint = crypto
+        .createHash('sha256')
+        .update(`POST /v1/access-passes ${JSON.stringify(payload)}`)
+        .digest('hex');
+      await redis.set(storageKey, JSON.stringify({ fingerprint }), 'PX', 500);
+
+      const inUse = await issue(payload, idempotencyKey).expect(409);
+      expect(inUse.body.error).toHaveProperty('code', 'IDEMPOTENCY_KEY_IN_USE');
+
+      await new Promise((resolve) => setTimeout(resolve, 600));
+      const setSpy = vi.spyOn(redis, 'set');
+      try {
+        await issue(payload, idempotencyKey).expect(201);
+
+        // Claims expire quickly; only the stored response is kept for 24 hours
+        expect(setSpy.mock.calls).toContainEqual([
+          storageKey,
+          expect.any(String),
+          'EX',
+          PENDING_TTL_SECONDS,
+          'NX',
+        ]);
+      } finally {
+        setSpy.mockRestore();
+      }
+      const ttl = await redis.ttl(storageKey);
+      expect(ttl).toBeGreaterThan(PENDING_TTL_SECONDS);
+      expect(ttl).toBeLessThanOrEqual(IDEMPOTENCY_TTL_SECONDS);
+    });
+
+    it('should reject keyed requests while the idempotency store is down', async () => {
+      const setSpy = vi
+        .spyOn(redis, 'set')
+        .mockRejectedValueOnce(new Error('Connection is closed.'));
+
+      try {
+        const response = await issue(issuePayload('Store Down User'), `issue-down-${Date.now()}`)
+          .expect('Content-Type', /json/)
+          .expect(503);
+
+        expect(response.body).toHaveProperty('success', false);
+        expect(response.body.error).toHaveProperty('code', 'SERVICE_UNAVAILABLE');
+      } finally {
+        setSpy.mockRestore();
+      }
+    });
+
+    it('should issue separate access passes for different keys', async () => {
+      const payload = issuePayload('Twice Issued User');
+
+      const first = await issue(payload, `issue-a-${Date.now()}`).expect(201);
+      const second = await issue(payload, `issue-b-${Date.now()}`).expect(201);
+
+      expect(second.body.data.id).not.toBe(first.body.data.id);
+    });
+  });
+
   describe('GET /v1/access-passes - List Access Passes', () => {
     let createdAccessPass: any;
 
@@ -201,6 +311,65 @@ describe('Access Pass API Integration Tests', () => {
     });
   });
 
+  describe('GET /v1/access-passes/:id - Get Access Pass', () => {
+    let accessPassToGet: any;
+
+    beforeAll(async () => {
+      accessPassToGet = await createTestAccessPass(
+        testAccount.account.id,
+        testCardTemplate.id
+      );
+    });
+
+    it('should get an access pass by id', async () => {
+      const { headers, query } = generateAuthHeadersForGet(
+        testAccount.accountId,
+        testAccount.sharedSecret,
+        { id: accessPassToGet.exId }
+      );
+
+      const response = await request(app)
+        .get(`/v1/access-passes/${accessPassToGet.exId}`)
+        .set(headers)
+        .query(query)
+        .expect('Content-Type', /json/)
+        .expect(200);
+
+      expect(response.body).toHaveProperty('success', true);
+      expect(response.body.data).toHaveProperty('id', accessPassToGet.exId);
+      expect(response.body.data).toHaveProperty('card_template_id', testCardTemplate.exId);
+      expect(response.body.data).toHaveProperty('full_name', accessPassToGet.fullName);
+      expect(response.body.data).toHaveProperty('state');
+    });
+
+    it('should return 404 for non-existent access pass', async () => {
+      const { headers, query } = generateAuthHeadersForGet(
+        testAccount.accountId,
+        testAccount.sharedSecret,
+        { id: 'non-existent-id' }
+      );
+
+      const response = await request(app)
+        .get('/v1/access-passes/non-existent-id')
+        .set(headers)
+        .query(query)
+        .expect('Content-Type', /json/)
+        .expect(404);
+
+      expect(response.body).toHaveProperty('success', false);
+      expect(response.body.error).toHaveProperty('code', 'ACCESS_PASS_NOT_FOUND');
+    });
+
+    it('should fail without authentication', async () => {
+      const response = await request(app)
+        .get(`/v1/access-passes/${accessPassToGet.exId}`)
+        .expect('Content-Type', /json/)
+        .expect(401);
+
+      expect(response.body).toHaveProperty('success', false);
+    });
+  });
+
   describe('PATCH /v1/access-passes/:id - Update Access Pass', () => {
     let accessPassToUpdate: any;
 
@@ -1,16 +1,16 @@
 use wusul::{
-    Wusul,
     types::{
-        IssueAccessPassParams, ListAccessPassesParams, UpdateAccessPassParams,
-        AccessPassState, Classification,
+        AccessPassState, Classification, IssueAccessPassParams, ListAccessPassesParams,
+        UpdateAccessPassParams,
     },
+    Wusul,
 };
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize client
-    let account_id = std::env::var("WUSUL_ACCOUNT_ID")
-        .expect("WUSUL_ACCOUNT_ID environment variable not set");
+    let account_id =
+        std::env::var("WUSUL_ACCOUNT_ID").expect("WUSUL_ACCOUNT_ID environment variable not set");
     let shared_secret = std::env::var("WUSUL_SHARED_SECRET")
         .expect("WUSUL_SHARED_SECRET environment variable not set");
 
@@ -21,31 +21,35 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let issue_params = IssueAccessPassParams {
         card_template_id: "your_template_id".to_string(),
         full_name: "Jane Smith".to_string(),
-        start_date: "2024-01-01".to_string(),
-        expiration_date: "2024-12-31".to_string(),
+        start_date: "2024-01-01T00:00:00Z".to_string(),
+        expiration_date: "2024-12-31T23:59:59Z".to_string(),
         email: Some("jane.smith@example.com".to_string()),
         phone_number: Some("+9876543210".to_string()),
         employee_id: Some("EMP002".to_string()),
         classification: Some(Classification::FullTime),
-        site_code: Some("SITE-A".to_string()),
+        site_code: Some("100".to_string()),
+        card_number: Some("4242".to_string()),
         ..Default::default()
     };
 
     let new_pass = client.access_passes.issue(issue_params).await?;
-    println!("Created: {} (ID: {})", new_pass.full_name, new_pass.id);
+    println!("Created: {} ({:?})", new_pass.id, new_pass.state);
 
     // List active passes
     println!("\n=== Listing Active Passes ===");
-    let list_params = ListAccessPassesParams {
-        state: Some(AccessPassState::Active),
-        limit: Some(10),
-        ..Default::default()
-    };
+    let list_params = ListAccessPassesParams::new("your_template_id")
+        .with_state(AccessPassState::Active)
+        .with_limit(10);
 
-    let active_passes = client.access_passes.list(Some(list_params)).await?;
+    let active_passes = client.access_passes.list(list_params).await?;
     println!("Found {} active passes", active_passes.len());
     for pass in active_passes.iter() {
-        println!("  - {}: {} ({})", pass.id, pass.full_name, pass.employee_id.as_deref().unwrap_or("N/A"));
+        println!(
+            "  - {}: {} ({})",
+            pass.id,
+            pass.full_name.as_deref().unwrap_or("N/A"),
+            pass.employee_id.as_deref().unwrap_or("N/A")
+        );
     }
 
     // Update an access pass
@@ -53,32 +57,32 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let update_params = UpdateAccessPassParams {
         access_pass_id: new_pass.id.clone(),
         full_name: Some("Jane Doe-Smith".to_string()),
-        email: Some("jane.doesmith@example.com".to_string()),
+        title: Some("Engineering Manager".to_string()),
         ..Default::default()
     };
 
     let updated_pass = client.access_passes.update(update_params).await?;
-    println!("Updated: {}", updated_pass.full_name);
+    println!("Updated: {} ({:?})", updated_pass.id, updated_pass.state);
 
     // Suspend the pass
     println!("\n=== Suspending Access Pass ===");
     let suspend_response = client.access_passes.suspend(&new_pass.id).await?;
-    println!("Suspend result: {:?}", suspend_response.message);
+    println!("Suspend result: {:?}", suspend_response.state);
 
     // Resume the pass
     println!("\n=== Resuming Access Pass ===");
     let resume_response = client.access_passes.resume(&new_pass.id).await?;
-    println!("Resume result: {:?}", resume_response.message);
+    println!("Resume result: {:?}", resume_response.state);
 
     // Unlink the pass
     println!("\n=== Unlinking Access Pass ===");
     let unlink_response = client.access_passes.unlink(&new_pass.id).await?;
-    println!("Unlink result: {:?}", unlink_response.message);
+    println!("Unlink result: {:?}", unlink_response.state);
 
     // Delete the pass
     println!("\n=== Deleting Access Pass ===");
     let delete_response = client.access_passes.delete(&new_pass.id).await?;
-    println!("Delete result: {:?}", delete_response.message);
+    println!("Delete result: {:?}", delete_response.state);
 
     Ok(())
 }
@@ -1,10 +1,13 @@
-use wusul::{Wusul, types::IssueAccessPassParams};
+use wusul::{
+    types::{IssueAccessPassParams, ListAccessPassesParams},
+    Wusul,
+};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize the Wusul client
-    let account_id = std::env::var("WUSUL_ACCOUNT_ID")
-        .expect("WUSUL_ACCOUNT_ID environment variable not set");
+    let account_id =
+        std::env::var("WUSUL_ACCOUNT_ID").expect("WUSUL_ACCOUNT_ID environment variable not set");
     let shared_secret = std::env::var("WUSUL_SHARED_SECRET")
         .expect("WUSUL_SHARED_SECRET environment variable not set");
 
@@ -20,27 +23,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let params = IssueAccessPassParams {
         card_template_id: "your_template_id".to_string(),
         full_name: "John Doe".to_string(),
-        start_date: "2024-01-01".to_string(),
-        expiration_date: "2024-12-31".to_string(),
+        start_date: "2024-01-01T00:00:00Z".to_string(),
+        expiration_date: "2024-12-31T23:59:59Z".to_string(),
         email: Some("john.doe@example.com".to_string()),
         phone_number: Some("+1234567890".to_string()),
         employee_id: Some("EMP001".to_string()),
+        site_code: Some("100".to_string()),
+        card_number: Some("4242".to_string()),
         ..Default::default()
     };
 
     let access_pass = client.access_passes.issue(params).await?;
     println!("Created access pass:");
     println!("  ID: {}", access_pass.id);
-    println!("  Name: {}", access_pass.full_name);
     println!("  State: {:?}", access_pass.state);
-    println!("  URL: {:?}", access_pass.url);
+    println!("  Install URL: {:?}", access_pass.install_url);
 
-    // List all access passes
-    println!("\nListing all access passes...");
-    let passes = client.access_passes.list(None).await?;
+    // List the access passes issued from the template
+    println!("\nListing access passes...");
+    let list_params = ListAccessPassesParams::new("your_template_id");
+    let passes = client.access_passes.list(list_params).await?;
     println!("Total passes: {}", passes.len());
     for pass in passes.iter().take(5) {
-        println!("  - {} ({})", pass.full_name, pass.id);
+        println!(
+            "  - {} ({})",
+            pass.full_name.as_deref().unwrap_or("N/A"),
+            pass.id
+        );
     }
 
     Ok(())
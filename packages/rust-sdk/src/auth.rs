@@ -1,10 +1,67 @@
+//! Request signing.
+//!
+//! Two schemes are supported:
+//!
+//! - **v1** (the default, accepted by every API version): the signature is
+//!   `hex(SHA256(shared_secret || base64(payload)))`, where the payload is the
+//!   canonical JSON body, or the `sig_payload` query parameter for GET requests.
+//! - **v2** (opt-in with [`WusulConfig::with_signature_version`]): the
+//!   signature is `hex(HMAC-SHA256(shared_secret, string_to_sign))` over the
+//!   method, path, timestamp, nonce and body, see [`string_to_sign_v2`]. The
+//!   timestamp and nonce are sent in [`TIMESTAMP_HEADER`] and [`NONCE_HEADER`],
+//!   so a server can reject stale and replayed requests with a
+//!   [`SignatureVerifier`].
+//!
+//! Both send the signature in `X-PAYLOAD-SIG`; v2 requests also carry
+//! `X-SIG-VERSION: 2`.
+//!
+//! [`WusulConfig::with_signature_version`]: crate::WusulConfig::with_signature_version
+
+use crate::canonical_json::to_canonical_string;
+use crate::error::{ErrorBody, ErrorCode, Result, WusulError};
 use base64::{engine::general_purpose, Engine as _};
+use hmac::{Hmac, Mac};
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use subtle::ConstantTimeEq;
+
+/// Header carrying the request signature
+pub const SIGNATURE_HEADER: &str = "X-PAYLOAD-SIG";
+/// Header carrying the signature scheme version of v2 requests
+pub const SIGNATURE_VERSION_HEADER: &str = "X-SIG-VERSION";
+/// Header carrying the Unix timestamp, in seconds, a v2 request was signed at
+pub const TIMESTAMP_HEADER: &str = "X-SIG-TIMESTAMP";
+/// Header carrying the single-use nonce of a v2 request
+pub const NONCE_HEADER: &str = "X-SIG-NONCE";
+
+/// Request signing scheme
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SignatureVersion {
+    /// `SHA256(shared_secret || base64(payload))`
+    #[default]
+    V1,
+    /// HMAC-SHA256 over method, path, timestamp, nonce and body
+    V2,
+}
 
 /// Encode a payload to base64
+///
+/// The payload is serialized with the canonical JSON encoding shared with the
+/// API, see [`crate::canonical_json`].
 pub fn encode_payload(payload: &serde_json::Value) -> String {
-    let json_string = serde_json::to_string(payload).unwrap_or_else(|_| "{}".to_string());
-    general_purpose::STANDARD.encode(json_string.as_bytes())
+    encode_body(&to_canonical_string(payload))
+}
+
+/// Encode an already serialized request body to base64
+pub fn encode_body(body: &str) -> String {
+    general_purpose::STANDARD.encode(body.as_bytes())
+}
+
+/// The payload the API signs for requests without a body: `{"id": "0"}`
+pub fn default_payload() -> serde_json::Value {
+    serde_json::json!({ "id": "0" })
 }
 
 /// Create a signature for authentication
@@ -16,45 +73,267 @@ pub fn encode_payload(payload: &serde_json::Value) -> String {
 /// 4. Converting to hexadecimal string
 pub fn create_signature(shared_secret: &str, encoded_payload: &str) -> String {
     let mut hasher = Sha256::new();
-    hasher.update(format!("{}{}", shared_secret, encoded_payload).as_bytes());
+    hasher.update(shared_secret.as_bytes());
+    hasher.update(encoded_payload.as_bytes());
     let result = hasher.finalize();
     hex::encode(result)
 }
 
 /// Verify a signature
-pub fn verify_signature(
-    shared_secret: &str,
-    encoded_payload: &str,
-    signature: &str,
-) -> bool {
+///
+/// The comparison takes the same time wherever the signatures differ.
+pub fn verify_signature(shared_secret: &str, encoded_payload: &str, signature: &str) -> bool {
     let expected_signature = create_signature(shared_secret, encoded_payload);
-    expected_signature == signature
+    expected_signature
+        .as_bytes()
+        .ct_eq(signature.as_bytes())
+        .into()
+}
+
+/// The string a v2 signature is computed over
+///
+/// The method in upper case, the path including the query string, the Unix
+/// timestamp in seconds, the nonce and the body, separated by newlines:
+///
+/// ```text
+/// POST
+/// /v1/access-passes/pass_123/suspend
+/// 1704067200
+/// 6f1c0d3e-7d9a-4b6e-9c1f-2a3b4c5d6e7f
+/// {"id":"0"}
+/// ```
+pub fn string_to_sign_v2(
+    method: &str,
+    path: &str,
+    timestamp: i64,
+    nonce: &str,
+    body: &str,
+) -> String {
+    format!(
+        "{}\n{}\n{}\n{}\n{}",
+        method.to_ascii_uppercase(),
+        path,
+        timestamp,
+        nonce,
+        body
+    )
+}
+
+/// Create a v2 signature: hex encoded HMAC-SHA256 of [`string_to_sign_v2`]
+pub fn create_signature_v2(
+    shared_secret: &str,
+    method: &str,
+    path: &str,
+    timestamp: i64,
+    nonce: &str,
+    body: &str,
+) -> String {
+    let mac = hmac_v2(shared_secret, method, path, timestamp, nonce, body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn hmac_v2(
+    shared_secret: &str,
+    method: &str,
+    path: &str,
+    timestamp: i64,
+    nonce: &str,
+    body: &str,
+) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(shared_secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(string_to_sign_v2(method, path, timestamp, nonce, body).as_bytes());
+    mac
+}
+
+/// The parts of a received v2 request that its signature covers
+#[derive(Debug, Clone, Copy)]
+pub struct SignedRequest<'a> {
+    /// HTTP method
+    pub method: &'a str,
+    /// Path including the query string, as received
+    pub path: &'a str,
+    /// Value of the [`TIMESTAMP_HEADER`] header
+    pub timestamp: &'a str,
+    /// Value of the [`NONCE_HEADER`] header
+    pub nonce: &'a str,
+    /// Raw request body; empty for requests without one
+    pub body: &'a str,
+    /// Value of the `X-PAYLOAD-SIG` header
+    pub signature: &'a str,
+}
+
+/// Server-side verification of v2 signatures
+///
+/// Rejects requests signed outside the clock-skew window, requests whose
+/// signature does not match, and nonces that were already used within the
+/// window. Nonces are remembered in memory, so servers running several
+/// instances need a shared verifier or their own replay check.
+///
+/// # Example
+///
+/// ```
+/// use wusul::auth::{create_signature_v2, SignatureVerifier, SignedRequest};
+///
+/// let verifier = SignatureVerifier::default();
+/// let now = chrono::Utc::now().timestamp();
+/// let signature = create_signature_v2("secret", "POST", "/v1/hooks", now, "nonce-1", "{}");
+///
+/// let request = SignedRequest {
+///     method: "POST",
+///     path: "/v1/hooks",
+///     timestamp: &now.to_string(),
+///     nonce: "nonce-1",
+///     body: "{}",
+///     signature: &signature,
+/// };
+/// assert!(verifier.verify("secret", &request).is_ok());
+/// // The same request again is a replay
+/// assert!(verifier.verify("secret", &request).is_err());
+/// ```
+#[derive(Debug)]
+pub struct SignatureVerifier {
+    max_skew: Duration,
+    /// Nonces seen within the window, with their timestamps
+    nonces: Mutex<HashMap<String, i64>>,
+}
+
+impl Default for SignatureVerifier {
+    /// Allows five minutes of clock skew
+    fn default() -> Self {
+        Self::new(Duration::from_secs(5 * 60))
+    }
+}
+
+impl SignatureVerifier {
+    /// Accept timestamps up to `max_skew` before or after the current time
+    pub fn new(max_skew: Duration) -> Self {
+        Self {
+            max_skew,
+            nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Verify `request` against the current time
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WusulError::AuthError`] if the timestamp is malformed or
+    /// outside the clock-skew window, the signature does not match, or the
+    /// nonce was already used.
+    pub fn verify(&self, shared_secret: &str, request: &SignedRequest<'_>) -> Result<()> {
+        self.verify_at(shared_secret, request, chrono::Utc::now().timestamp())
+    }
+
+    /// Verify `request` as if the current Unix time were `now`
+    ///
+    /// # Errors
+    ///
+    /// See [`verify`](Self::verify).
+    pub fn verify_at(
+        &self,
+        shared_secret: &str,
+        request: &SignedRequest<'_>,
+        now: i64,
+    ) -> Result<()> {
+        let timestamp: i64 = request
+            .timestamp
+            .trim()
+            .parse()
+            .map_err(|_| unauthorized("Invalid signature timestamp"))?;
+        let skew = i64::try_from(self.max_skew.as_secs()).unwrap_or(i64::MAX);
+        if now.abs_diff(timestamp) > skew.unsigned_abs() {
+            return Err(unauthorized(
+                "Signature timestamp outside the allowed clock skew",
+            ));
+        }
+
+        let signature =
+            hex::decode(request.signature).map_err(|_| unauthorized("Invalid signature"))?;
+        hmac_v2(
+            shared_secret,
+            request.method,
+            request.path,
+            timestamp,
+            request.nonce,
+            request.body,
+        )
+        .verify_slice(&signature)
+        .map_err(|_| unauthorized("Invalid signature"))?;
+
+        // Only authentic requests are remembered, so forged ones cannot burn nonces
+        let mut nonces = self.nonces.lock().unwrap();
+        nonces.retain(|_, seen| now.abs_diff(*seen) <= skew.unsigned_abs());
+        if nonces
+            .insert(request.nonce.to_string(), timestamp)
+            .is_some()
+        {
+            return Err(unauthorized("Signature nonce already used"));
+        }
+        Ok(())
+    }
+}
+
+fn unauthorized(message: &str) -> WusulError {
+    WusulError::AuthError(ErrorBody {
+        code: ErrorCode::Unauthorized,
+        message: message.to_string(),
+        details: None,
+    })
 }
 
 /// Create authentication headers for POST/PATCH requests
+///
+/// Returns the account ID, the signature and the canonical JSON body that was
+/// signed. The body must be sent byte-for-byte as the request body, because the
+/// API re-encodes what it receives and compares signatures.
+///
+/// The API signs its default payload instead of an empty body, so a missing
+/// payload or an empty object is replaced by [`default_payload`].
 pub fn create_auth_headers(
     account_id: &str,
     shared_secret: &str,
     payload: Option<&serde_json::Value>,
-) -> (String, String) {
-    let encoded_payload = if let Some(p) = payload {
-        encode_payload(p)
-    } else {
-        encode_payload(&serde_json::json!({}))
-    };
+) -> (String, String, String) {
+    let body = canonical_body(payload);
+    let signature = create_signature(shared_secret, &encode_body(&body));
+
+    (account_id.to_string(), signature, body)
+}
 
-    let signature = create_signature(shared_secret, &encoded_payload);
+/// The request body sent for `payload`: its canonical JSON, or the default
+/// payload when it is missing or an empty object
+pub fn canonical_body(payload: Option<&serde_json::Value>) -> String {
+    match payload {
+        Some(p) if !is_empty_object(p) => to_canonical_string(p),
+        _ => to_canonical_string(&default_payload()),
+    }
+}
 
-    (account_id.to_string(), signature)
+fn is_empty_object(value: &serde_json::Value) -> bool {
+    value.as_object().is_some_and(|obj| obj.is_empty())
 }
 
 /// Create authentication headers for GET requests
+///
+/// GET requests have no body, so the API verifies the signature against the
+/// `sig_payload` query parameter instead. Returns the account ID, the signature
+/// and the base64 encoded payload that was signed, which must be sent verbatim
+/// as `sig_payload`.
 pub fn create_get_auth_headers(
     account_id: &str,
     shared_secret: &str,
     query_params: Option<&serde_json::Value>,
-) -> (String, String) {
-    create_auth_headers(account_id, shared_secret, query_params)
+) -> (String, String, String) {
+    let sig_payload = if let Some(p) = query_params {
+        encode_payload(p)
+    } else {
+        encode_payload(&serde_json::json!({}))
+    };
+
+    let signature = create_signature(shared_secret, &sig_payload);
+
+    (account_id.to_string(), signature, sig_payload)
 }
 
 #[cfg(test)]
@@ -108,6 +387,113 @@ mod tests {
         assert!(!verify_signature(secret, "different", &signature));
     }
 
+    #[test]
+    fn test_verify_signature_rejects_prefixes() {
+        let signature = create_signature("test_secret", "test_payload");
+
+        assert!(!verify_signature(
+            "test_secret",
+            "test_payload",
+            &signature[..63]
+        ));
+        assert!(!verify_signature("test_secret", "test_payload", ""));
+    }
+
+    #[test]
+    fn test_string_to_sign_v2() {
+        assert_eq!(
+            string_to_sign_v2("post", "/v1/access-passes?x=1", 1704067200, "n1", "{}"),
+            "POST\n/v1/access-passes?x=1\n1704067200\nn1\n{}"
+        );
+    }
+
+    #[test]
+    fn test_create_signature_v2_is_hmac() {
+        // HMAC-SHA256("key", "POST\n/p\n0\nn\n")
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"key").unwrap();
+        mac.update(b"POST\n/p\n0\nn\n");
+        let expected = hex::encode(mac.finalize().into_bytes());
+
+        assert_eq!(
+            create_signature_v2("key", "POST", "/p", 0, "n", ""),
+            expected
+        );
+    }
+
+    fn signed<'a>(signature: &'a str, timestamp: &'a str, nonce: &'a str) -> SignedRequest<'a> {
+        SignedRequest {
+            method: "POST",
+            path: "/v1/access-passes/pass_123/suspend",
+            timestamp,
+            nonce,
+            body: r#"{"id":"0"}"#,
+            signature,
+        }
+    }
+
+    fn auth_message(result: Result<()>) -> String {
+        match result {
+            Err(WusulError::AuthError(error)) => error.message,
+            other => panic!("Expected AuthError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_verifier_accepts_within_skew_and_rejects_replays() {
+        let verifier = SignatureVerifier::new(Duration::from_secs(60));
+        let signature = create_signature_v2(
+            "secret",
+            "POST",
+            "/v1/access-passes/pass_123/suspend",
+            1_000,
+            "n1",
+            r#"{"id":"0"}"#,
+        );
+        let request = signed(&signature, "1000", "n1");
+
+        assert!(verifier.verify_at("secret", &request, 1_060).is_ok());
+        assert_eq!(
+            auth_message(verifier.verify_at("secret", &request, 1_060)),
+            "Signature nonce already used"
+        );
+    }
+
+    #[test]
+    fn test_verifier_rejects_stale_and_forged_requests() {
+        let verifier = SignatureVerifier::new(Duration::from_secs(60));
+        let signature = create_signature_v2(
+            "secret",
+            "POST",
+            "/v1/access-passes/pass_123/suspend",
+            1_000,
+            "n1",
+            r#"{"id":"0"}"#,
+        );
+
+        assert_eq!(
+            auth_message(verifier.verify_at("secret", &signed(&signature, "1000", "n1"), 1_061)),
+            "Signature timestamp outside the allowed clock skew"
+        );
+        assert_eq!(
+            auth_message(verifier.verify_at("secret", &signed(&signature, "soon", "n1"), 1_000)),
+            "Invalid signature timestamp"
+        );
+        assert_eq!(
+            auth_message(verifier.verify_at("other", &signed(&signature, "1000", "n1"), 1_000)),
+            "Invalid signature"
+        );
+        // A different nonce than the one signed
+        assert_eq!(
+            auth_message(verifier.verify_at("secret", &signed(&signature, "1000", "n2"), 1_000)),
+            "Invalid signature"
+        );
+
+        // Rejected requests did not use up the nonce
+        assert!(verifier
+            .verify_at("secret", &signed(&signature, "1000", "n1"), 1_000)
+            .is_ok());
+    }
+
     #[test]
     fn test_create_auth_headers() {
         let account_id = "test_account";
@@ -116,10 +502,12 @@ mod tests {
             "key": "value"
         });
 
-        let (acct_id, signature) = create_auth_headers(account_id, secret, Some(&payload));
+        let (acct_id, signature, body) = create_auth_headers(account_id, secret, Some(&payload));
 
         assert_eq!(acct_id, account_id);
         assert_eq!(signature.len(), 64); // SHA256 hex string
+        assert_eq!(body, r#"{"key":"value"}"#);
+        assert!(verify_signature(secret, &encode_body(&body), &signature));
     }
 
     #[test]
@@ -127,10 +515,16 @@ mod tests {
         let account_id = "test_account";
         let secret = "test_secret";
 
-        let (acct_id, signature) = create_auth_headers(account_id, secret, None);
+        let (acct_id, signature, body) = create_auth_headers(account_id, secret, None);
 
         assert_eq!(acct_id, account_id);
         assert_eq!(signature.len(), 64);
+        assert_eq!(body, r#"{"id":"0"}"#);
+
+        let (_, empty_signature, empty_body) =
+            create_auth_headers(account_id, secret, Some(&json!({})));
+        assert_eq!(empty_body, body);
+        assert_eq!(empty_signature, signature);
     }
 
     #[test]
@@ -147,4 +541,47 @@ mod tests {
 
         assert_eq!(sig1, sig2);
     }
+
+    #[test]
+    fn test_create_get_auth_headers_sig_payload() {
+        let query = json!({
+            "template_id": "template_123",
+            "state": "ACTIVE"
+        });
+
+        let (acct_id, signature, sig_payload) =
+            create_get_auth_headers("test_account", "test_secret", Some(&query));
+
+        assert_eq!(acct_id, "test_account");
+        assert_eq!(sig_payload, encode_payload(&query));
+        assert!(verify_signature("test_secret", &sig_payload, &signature));
+
+        let decoded = general_purpose::STANDARD.decode(&sig_payload).unwrap();
+        let decoded: serde_json::Value = serde_json::from_slice(&decoded).unwrap();
+        assert_eq!(decoded, query);
+    }
+
+    #[test]
+    fn test_create_get_auth_headers_no_params() {
+        let (_, signature, sig_payload) =
+            create_get_auth_headers("test_account", "test_secret", None);
+
+        assert_eq!(sig_payload, encode_payload(&json!({})));
+        assert!(verify_signature("test_secret", &sig_payload, &signature));
+    }
+
+    #[test]
+    fn test_encode_payload_is_canonical() {
+        let payload: serde_json::Value =
+            serde_json::from_str(r#"{"b": 1.0, "a": "\u00e9", "10": [], "9": null}"#).unwrap();
+
+        let decoded = general_purpose::STANDARD
+            .decode(encode_payload(&payload))
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(decoded).unwrap(),
+            r#"{"9":null,"10":[],"a":"é","b":1}"#
+        );
+    }
 }
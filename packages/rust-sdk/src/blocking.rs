@@ -0,0 +1,474 @@
+//! Blocking client, enabled by the `blocking` feature.
+//!
+//! [`Wusul`] mirrors the async [`crate::Wusul`] for synchronous programs such
+//! as CLIs and cron jobs. It wraps the async client and drives it on a private
+//! single-threaded tokio runtime, so signing, retries, middleware, types and
+//! errors are exactly those of the async client.
+//!
+//! The blocking client must not be used from within an async runtime: calling
+//! it from an async task panics. Use the async client there instead.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use wusul::blocking::Wusul;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = Wusul::new(
+//!     "your_account_id".to_string(),
+//!     "your_shared_secret".to_string()
+//! )?;
+//!
+//! let access_pass = client.access_passes.suspend("pass_123")?;
+//! println!("Pass state: {:?}", access_pass.state);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Result, WusulError};
+use crate::http_client::HttpClient;
+use crate::pagination::{Page, DEFAULT_PREFETCH};
+use crate::resources;
+use crate::resources::access_passes::DEFAULT_CONCURRENCY;
+use crate::types::{
+    AccessPass, ApiResult, CardTemplate, CreateCardTemplateParams, EventLogEntry,
+    IssueAccessPassParams, ListAccessPassesParams, ReadEventLogParams, RequestOptions,
+    UpdateAccessPassParams, UpdateCardTemplateParams,
+};
+use crate::WusulConfig;
+use futures::stream::{BoxStream, StreamExt};
+use std::fmt;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Blocking client for interacting with the Wusul API
+///
+/// See [`crate::Wusul`] for the async equivalent.
+pub struct Wusul {
+    http: Arc<HttpClient>,
+    runtime: Arc<Runtime>,
+    /// Resource for managing access passes
+    pub access_passes: AccessPasses,
+    /// Resource for console operations (Enterprise tier)
+    pub console: Console,
+}
+
+impl Wusul {
+    /// Create a new blocking client with default configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Account ID or shared secret is empty
+    /// - HTTP client or runtime cannot be initialized
+    pub fn new(account_id: String, shared_secret: String) -> Result<Self> {
+        Self::with_config(WusulConfig::new(account_id, shared_secret))
+    }
+
+    /// Create a new blocking client with custom configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Account ID or shared secret is empty
+    /// - HTTP client or runtime cannot be initialized
+    pub fn with_config(config: WusulConfig) -> Result<Self> {
+        let crate::Wusul {
+            http,
+            access_passes,
+            console,
+        } = crate::Wusul::with_config(config)?;
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                WusulError::ConfigError(format!("Failed to start the blocking runtime: {}", e))
+            })?;
+        let runtime = Arc::new(runtime);
+
+        Ok(Self {
+            http,
+            access_passes: AccessPasses {
+                inner: access_passes,
+                runtime: Arc::clone(&runtime),
+            },
+            console: Console {
+                inner: console,
+                runtime: Arc::clone(&runtime),
+            },
+            runtime,
+        })
+    }
+
+    /// Check the health status of the Wusul API
+    pub fn health(&self) -> Result<serde_json::Value> {
+        self.runtime.block_on(self.http.get("/health", None))
+    }
+}
+
+/// Blocking resource for managing access passes
+///
+/// See [`resources::AccessPasses`] for details on each method.
+pub struct AccessPasses {
+    inner: resources::AccessPasses,
+    runtime: Arc<Runtime>,
+}
+
+impl AccessPasses {
+    /// Issue a new access pass
+    pub fn issue(&self, params: IssueAccessPassParams) -> Result<AccessPass> {
+        self.runtime.block_on(self.inner.issue(params))
+    }
+
+    /// Issue a new access pass with per-call request options
+    pub fn issue_with_options(
+        &self,
+        params: IssueAccessPassParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        self.runtime
+            .block_on(self.inner.issue_with_options(params, options))
+    }
+
+    /// Retrieve a single access pass
+    pub fn get(&self, access_pass_id: &str) -> Result<AccessPass> {
+        self.runtime.block_on(self.inner.get(access_pass_id))
+    }
+
+    /// Retrieve a single access pass with per-call request options
+    pub fn get_with_options(
+        &self,
+        access_pass_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        self.runtime
+            .block_on(self.inner.get_with_options(access_pass_id, options))
+    }
+
+    /// Retrieve several access passes by ID, each with its own result
+    pub fn get_many<I>(&self, access_pass_ids: I) -> Vec<(String, Result<AccessPass>)>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.get_many_with_options(
+            access_pass_ids,
+            &RequestOptions::default(),
+            DEFAULT_CONCURRENCY,
+        )
+    }
+
+    /// Retrieve several access passes by ID with per-call request options
+    pub fn get_many_with_options<I>(
+        &self,
+        access_pass_ids: I,
+        options: &RequestOptions,
+        concurrency: usize,
+    ) -> Vec<(String, Result<AccessPass>)>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.runtime.block_on(self.inner.get_many_with_options(
+            access_pass_ids,
+            options,
+            concurrency,
+        ))
+    }
+
+    /// List access passes with optional filtering
+    pub fn list(&self, params: ListAccessPassesParams) -> Result<Vec<AccessPass>> {
+        self.runtime.block_on(self.inner.list(params))
+    }
+
+    /// List access passes with per-call request options
+    pub fn list_with_options(
+        &self,
+        params: ListAccessPassesParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<Vec<AccessPass>>> {
+        self.runtime
+            .block_on(self.inner.list_with_options(params, options))
+    }
+
+    /// Fetch one page of access passes
+    pub fn list_page(&self, params: ListAccessPassesParams) -> Result<Page<AccessPass>> {
+        self.runtime.block_on(self.inner.list_page(params))
+    }
+
+    /// Fetch one page of access passes with per-call request options
+    pub fn list_page_with_options(
+        &self,
+        params: ListAccessPassesParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<Page<AccessPass>>> {
+        self.runtime
+            .block_on(self.inner.list_page_with_options(params, options))
+    }
+
+    /// Iterate over the access passes of every page
+    pub fn list_all(&self, params: ListAccessPassesParams) -> PageIter<AccessPass> {
+        self.list_all_with_options(params, &RequestOptions::default(), DEFAULT_PREFETCH)
+    }
+
+    /// Iterate over the access passes of every page with per-call request options
+    pub fn list_all_with_options(
+        &self,
+        params: ListAccessPassesParams,
+        options: &RequestOptions,
+        prefetch: usize,
+    ) -> PageIter<AccessPass> {
+        PageIter {
+            stream: self.inner.list_all_with_options(params, options, prefetch),
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+
+    /// Update an existing access pass
+    pub fn update(&self, params: UpdateAccessPassParams) -> Result<AccessPass> {
+        self.runtime.block_on(self.inner.update(params))
+    }
+
+    /// Update an existing access pass with per-call request options
+    pub fn update_with_options(
+        &self,
+        params: UpdateAccessPassParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        self.runtime
+            .block_on(self.inner.update_with_options(params, options))
+    }
+
+    /// Suspend an access pass
+    pub fn suspend(&self, access_pass_id: &str) -> Result<AccessPass> {
+        self.runtime.block_on(self.inner.suspend(access_pass_id))
+    }
+
+    /// Suspend an access pass with per-call request options
+    pub fn suspend_with_options(
+        &self,
+        access_pass_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        self.runtime
+            .block_on(self.inner.suspend_with_options(access_pass_id, options))
+    }
+
+    /// Resume a suspended access pass
+    pub fn resume(&self, access_pass_id: &str) -> Result<AccessPass> {
+        self.runtime.block_on(self.inner.resume(access_pass_id))
+    }
+
+    /// Resume a suspended access pass with per-call request options
+    pub fn resume_with_options(
+        &self,
+        access_pass_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        self.runtime
+            .block_on(self.inner.resume_with_options(access_pass_id, options))
+    }
+
+    /// Unlink an access pass from the device
+    pub fn unlink(&self, access_pass_id: &str) -> Result<AccessPass> {
+        self.runtime.block_on(self.inner.unlink(access_pass_id))
+    }
+
+    /// Unlink an access pass from the device with per-call request options
+    pub fn unlink_with_options(
+        &self,
+        access_pass_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        self.runtime
+            .block_on(self.inner.unlink_with_options(access_pass_id, options))
+    }
+
+    /// Permanently delete an access pass
+    pub fn delete(&self, access_pass_id: &str) -> Result<AccessPass> {
+        self.runtime.block_on(self.inner.delete(access_pass_id))
+    }
+
+    /// Permanently delete an access pass with per-call request options
+    pub fn delete_with_options(
+        &self,
+        access_pass_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        self.runtime
+            .block_on(self.inner.delete_with_options(access_pass_id, options))
+    }
+}
+
+/// Blocking resource for enterprise console operations (template management)
+///
+/// See [`resources::Console`] for details on each method.
+pub struct Console {
+    inner: resources::Console,
+    runtime: Arc<Runtime>,
+}
+
+impl Console {
+    /// Create a new card template
+    pub fn create_template(&self, params: CreateCardTemplateParams) -> Result<CardTemplate> {
+        self.runtime.block_on(self.inner.create_template(params))
+    }
+
+    /// Create a new card template with per-call request options
+    pub fn create_template_with_options(
+        &self,
+        params: CreateCardTemplateParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<CardTemplate>> {
+        self.runtime
+            .block_on(self.inner.create_template_with_options(params, options))
+    }
+
+    /// Read a card template by ID
+    pub fn read_template(&self, card_template_id: &str) -> Result<CardTemplate> {
+        self.runtime
+            .block_on(self.inner.read_template(card_template_id))
+    }
+
+    /// Read a card template by ID with per-call request options
+    pub fn read_template_with_options(
+        &self,
+        card_template_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<CardTemplate>> {
+        self.runtime.block_on(
+            self.inner
+                .read_template_with_options(card_template_id, options),
+        )
+    }
+
+    /// Update an existing card template
+    pub fn update_template(&self, params: UpdateCardTemplateParams) -> Result<CardTemplate> {
+        self.runtime.block_on(self.inner.update_template(params))
+    }
+
+    /// Update an existing card template with per-call request options
+    pub fn update_template_with_options(
+        &self,
+        params: UpdateCardTemplateParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<CardTemplate>> {
+        self.runtime
+            .block_on(self.inner.update_template_with_options(params, options))
+    }
+
+    /// Publish a card template to make it available for use
+    pub fn publish_template(&self, card_template_id: &str) -> Result<CardTemplate> {
+        self.runtime
+            .block_on(self.inner.publish_template(card_template_id))
+    }
+
+    /// Publish a card template with per-call request options
+    pub fn publish_template_with_options(
+        &self,
+        card_template_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<CardTemplate>> {
+        self.runtime.block_on(
+            self.inner
+                .publish_template_with_options(card_template_id, options),
+        )
+    }
+
+    /// Read the event log of a card template with optional filtering
+    pub fn event_log(&self, params: ReadEventLogParams) -> Result<Vec<EventLogEntry>> {
+        self.runtime.block_on(self.inner.event_log(params))
+    }
+
+    /// Read the event log of a card template with per-call request options
+    pub fn event_log_with_options(
+        &self,
+        params: ReadEventLogParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<Vec<EventLogEntry>>> {
+        self.runtime
+            .block_on(self.inner.event_log_with_options(params, options))
+    }
+
+    /// Fetch one page of the event log of a card template
+    pub fn event_log_page(&self, params: ReadEventLogParams) -> Result<Page<EventLogEntry>> {
+        self.runtime.block_on(self.inner.event_log_page(params))
+    }
+
+    /// Fetch one page of the event log of a card template with per-call request options
+    pub fn event_log_page_with_options(
+        &self,
+        params: ReadEventLogParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<Page<EventLogEntry>>> {
+        self.runtime
+            .block_on(self.inner.event_log_page_with_options(params, options))
+    }
+
+    /// Iterate over the events of every page of a card template's event log
+    pub fn event_log_all(&self, params: ReadEventLogParams) -> PageIter<EventLogEntry> {
+        self.event_log_all_with_options(params, &RequestOptions::default(), DEFAULT_PREFETCH)
+    }
+
+    /// Iterate over the events of every page of a card template's event log with per-call request options
+    pub fn event_log_all_with_options(
+        &self,
+        params: ReadEventLogParams,
+        options: &RequestOptions,
+        prefetch: usize,
+    ) -> PageIter<EventLogEntry> {
+        PageIter {
+            stream: self
+                .inner
+                .event_log_all_with_options(params, options, prefetch),
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+}
+
+/// Iterator over the items of every page of a list endpoint
+///
+/// Returned by the `*_all` methods. Pages are requested as the iterator
+/// advances; it ends after yielding the first error.
+pub struct PageIter<T> {
+    stream: BoxStream<'static, Result<T>>,
+    runtime: Arc<Runtime>,
+}
+
+impl<T> Iterator for PageIter<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl<T> fmt::Debug for PageIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PageIter").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocking_client_creation() {
+        let client = Wusul::new("account_id".to_string(), "shared_secret".to_string());
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn test_blocking_client_validates_config() {
+        let result = Wusul::new(String::new(), "shared_secret".to_string());
+        assert!(matches!(result, Err(WusulError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_blocking_client_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Wusul>();
+    }
+}
@@ -0,0 +1,231 @@
+//! Canonical JSON encoding shared with the Wusul API.
+//!
+//! The API verifies payload signatures by re-encoding the parsed request body
+//! with `JSON.stringify`. To make the signature independent of how the body was
+//! produced, the SDK encodes every signed payload canonically and sends those
+//! exact bytes. The encoding is chosen so that `JSON.stringify(JSON.parse(x))`
+//! returns `x` unchanged:
+//!
+//! - No insignificant whitespace.
+//! - Object keys that are array indices (`"0"`, `"1"`, ... below 2^32 - 1) come
+//!   first in ascending numeric order, as JavaScript engines enumerate them;
+//!   all other keys follow in Unicode code point order.
+//! - Numbers are formatted like JavaScript's `Number.prototype.toString`: `1.0`
+//!   becomes `1`, `-0` becomes `0`, exponents are used below `1e-6` and from
+//!   `1e21` upwards (`1e+21`, `1.5e-7`). Integers beyond 2^53 are rounded to the
+//!   nearest double, exactly as the API will see them.
+//! - Strings escape only `"`, `\` and control characters (`\b`, `\f`, `\n`,
+//!   `\r`, `\t`, otherwise `\u00xx`); all other characters are written as UTF-8.
+
+use serde_json::Value;
+
+/// The largest integer that JavaScript numbers represent exactly (2^53)
+const MAX_SAFE_INTEGER: u64 = 1 << 53;
+
+/// Encode a JSON value canonically
+pub fn to_canonical_string(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write_number(out, n),
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(obj) => {
+            let mut entries: Vec<(&String, &Value)> = obj.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| match (array_index(a), array_index(b)) {
+                (Some(x), Some(y)) => x.cmp(&y),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => a.cmp(b),
+            });
+
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, item);
+            }
+            out.push('}');
+        }
+    }
+}
+
+/// Parse a key that JavaScript treats as an array index
+fn array_index(key: &str) -> Option<u32> {
+    if key.is_empty() || (key.len() > 1 && key.starts_with('0')) {
+        return None;
+    }
+    if !key.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    key.parse::<u32>().ok().filter(|index| *index != u32::MAX)
+}
+
+fn write_number(out: &mut String, n: &serde_json::Number) {
+    if let Some(i) = n.as_i64() {
+        if i.unsigned_abs() <= MAX_SAFE_INTEGER {
+            out.push_str(&i.to_string());
+            return;
+        }
+    } else if let Some(u) = n.as_u64() {
+        if u <= MAX_SAFE_INTEGER {
+            out.push_str(&u.to_string());
+            return;
+        }
+    }
+
+    // serde_json never stores non-finite numbers, so this is always a real value
+    out.push_str(&format_js_number(n.as_f64().unwrap_or(0.0)));
+}
+
+/// Format a finite double the way JavaScript's `Number.prototype.toString` does
+pub(crate) fn format_js_number(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    if value < 0.0 {
+        return format!("-{}", format_js_number(-value));
+    }
+
+    // `{:e}` yields the shortest round-trip digits, e.g. `1.5e-7` or `1.23456e2`
+    let scientific = format!("{:e}", value);
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("exponent formatting always contains 'e'");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let exponent: i32 = exponent.parse().expect("exponent is an integer");
+
+    let k = digits.len() as i32;
+    let n = exponent + 1;
+
+    if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        let (int_part, frac_part) = digits.split_at(n as usize);
+        format!("{}.{}", int_part, frac_part)
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat((-n) as usize), digits)
+    } else {
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        let (first, rest) = digits.split_at(1);
+        if rest.is_empty() {
+            format!("{}e{}{}", first, sign, (n - 1).abs())
+        } else {
+            format!("{}.{}e{}{}", first, rest, sign, (n - 1).abs())
+        }
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0C}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_key_ordering() {
+        let value = json!({"b": 1, "a": 2, "10": 3, "9": 4, "01": 5, "A": 6});
+        assert_eq!(
+            to_canonical_string(&value),
+            r#"{"9":4,"10":3,"01":5,"A":6,"a":2,"b":1}"#
+        );
+    }
+
+    #[test]
+    fn test_nested_values() {
+        let value = json!({"z": [1, {"y": null, "x": true}], "a": {}, "m": []});
+        assert_eq!(
+            to_canonical_string(&value),
+            r#"{"a":{},"m":[],"z":[1,{"x":true,"y":null}]}"#
+        );
+    }
+
+    #[test]
+    fn test_number_formatting() {
+        let cases = [
+            (1.0, "1"),
+            (-1.0, "-1"),
+            (-0.0, "0"),
+            (1.5, "1.5"),
+            (0.1 + 0.2, "0.30000000000000004"),
+            (123.456, "123.456"),
+            (0.000001, "0.000001"),
+            (0.0000015, "0.0000015"),
+            (1.5e-7, "1.5e-7"),
+            (1e-7, "1e-7"),
+            (1e20, "100000000000000000000"),
+            (1e21, "1e+21"),
+            (1.2345e25, "1.2345e+25"),
+            (f64::MAX, "1.7976931348623157e+308"),
+            (5e-324, "5e-324"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(format_js_number(input), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(to_canonical_string(&json!(42)), "42");
+        assert_eq!(to_canonical_string(&json!(-42)), "-42");
+        assert_eq!(
+            to_canonical_string(&json!(9007199254740992u64)),
+            "9007199254740992"
+        );
+        assert_eq!(
+            to_canonical_string(&json!(9007199254740993u64)),
+            "9007199254740992"
+        );
+        assert_eq!(
+            to_canonical_string(&json!(u64::MAX)),
+            "18446744073709552000"
+        );
+    }
+
+    #[test]
+    fn test_string_escaping() {
+        let value = json!(
+            "quote\" backslash\\ slash/ \n\r\t\u{08}\u{0C}\u{01}\u{1f}\u{7f} é مرحبا 🚪 \u{2028}"
+        );
+        assert_eq!(
+            to_canonical_string(&value),
+            "\"quote\\\" backslash\\\\ slash/ \\n\\r\\t\\b\\f\\u0001\\u001f\u{7f} é مرحبا 🚪 \u{2028}\""
+        );
+    }
+}
@@ -0,0 +1,317 @@
+//! Circuit breaker for sustained API outages.
+//!
+//! While the API is down, every request would otherwise wait for a connection
+//! error or the full timeout. A [`CircuitBreaker`] installed with
+//! [`WusulConfig::with_circuit_breaker`] counts consecutive failed attempts,
+//! meaning transport errors (connection failures, timeouts) and 5xx responses.
+//! After `failure_threshold` of them it opens, and requests fail immediately
+//! with [`WusulError::CircuitOpen`] without being sent.
+//!
+//! Once `cooldown` has passed the breaker half-opens and lets a single trial
+//! request through. If it succeeds the breaker closes again; if it fails the
+//! breaker reopens for another cooldown. Any response other than a 5xx,
+//! including 4xx errors, counts as a success: the API is reachable.
+//!
+//! Clones share their state, so one breaker can protect several clients.
+//!
+//! [`WusulConfig::with_circuit_breaker`]: crate::WusulConfig::with_circuit_breaker
+
+use crate::error::{Result, WusulError};
+use crate::transport::HttpResponse;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// State of a [`CircuitBreaker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are sent normally
+    Closed,
+    /// Requests fail fast with [`WusulError::CircuitOpen`]
+    Open,
+    /// A single trial request is allowed to test whether the API recovered
+    HalfOpen,
+}
+
+type StateListener = Arc<dyn Fn(CircuitState, CircuitState) + Send + Sync>;
+
+/// Fails requests fast after repeated transport or server errors
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use wusul::circuit_breaker::CircuitBreaker;
+/// use wusul::WusulConfig;
+///
+/// let breaker = CircuitBreaker::new(5, Duration::from_secs(30)).on_state_change(|from, to| {
+///     println!("Wusul circuit breaker: {:?} -> {:?}", from, to);
+/// });
+///
+/// let config = WusulConfig::new("account_id".to_string(), "shared_secret".to_string())
+///     .with_circuit_breaker(breaker);
+/// ```
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    state: Arc<Mutex<Breaker>>,
+    listener: Option<StateListener>,
+}
+
+struct Breaker {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Instant,
+    /// When the half-open trial request was let through
+    trial_started: Option<Instant>,
+}
+
+impl Default for CircuitBreaker {
+    /// Opens after 5 consecutive failures and half-opens after 30 seconds
+    fn default() -> Self {
+        Self::new(5, Duration::from_secs(30))
+    }
+}
+
+impl CircuitBreaker {
+    /// Open after `failure_threshold` consecutive failures, half-open after `cooldown`
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            state: Arc::new(Mutex::new(Breaker {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: Instant::now(),
+                trial_started: None,
+            })),
+            listener: None,
+        }
+    }
+
+    /// Call `listener` with the old and new state on every state change
+    ///
+    /// The listener runs on the task that caused the change, so it should be
+    /// quick: record a metric or log a line.
+    pub fn on_state_change<F>(mut self, listener: F) -> Self
+    where
+        F: Fn(CircuitState, CircuitState) + Send + Sync + 'static,
+    {
+        self.listener = Some(Arc::new(listener));
+        self
+    }
+
+    /// The current state
+    pub fn state(&self) -> CircuitState {
+        self.state.lock().unwrap().state
+    }
+
+    /// Number of consecutive failures counted so far
+    pub fn consecutive_failures(&self) -> u32 {
+        self.state.lock().unwrap().consecutive_failures
+    }
+
+    /// Check whether a request may be sent
+    pub(crate) fn admit(&self) -> Result<()> {
+        let now = Instant::now();
+        let (result, change) = {
+            let mut breaker = self.state.lock().unwrap();
+            match breaker.state {
+                CircuitState::Closed => (Ok(()), None),
+                CircuitState::Open => {
+                    let reopen_at = breaker.opened_at + self.cooldown;
+                    if now >= reopen_at {
+                        breaker.state = CircuitState::HalfOpen;
+                        breaker.trial_started = Some(now);
+                        (Ok(()), Some((CircuitState::Open, CircuitState::HalfOpen)))
+                    } else {
+                        let retry_after = reopen_at - now;
+                        (Err(WusulError::CircuitOpen { retry_after }), None)
+                    }
+                }
+                CircuitState::HalfOpen => match breaker.trial_started {
+                    // A trial that never reported back (e.g. a cancelled request)
+                    // does not hold the breaker half-open forever
+                    Some(started) if now < started + self.cooldown => {
+                        let retry_after = started + self.cooldown - now;
+                        (Err(WusulError::CircuitOpen { retry_after }), None)
+                    }
+                    _ => {
+                        breaker.trial_started = Some(now);
+                        (Ok(()), None)
+                    }
+                },
+            }
+        };
+
+        self.notify(change);
+        result
+    }
+
+    /// Count the outcome of a request attempt
+    pub(crate) fn record(&self, outcome: &Result<HttpResponse>) {
+        let failed = match outcome {
+            Ok(response) => response.status.is_server_error(),
+            Err(err) => is_transport_failure(err),
+        };
+
+        let change = {
+            let mut breaker = self.state.lock().unwrap();
+            let previous = breaker.state;
+            if failed {
+                breaker.consecutive_failures = breaker.consecutive_failures.saturating_add(1);
+                let trip = previous == CircuitState::HalfOpen
+                    || breaker.consecutive_failures >= self.failure_threshold;
+                if trip {
+                    breaker.state = CircuitState::Open;
+                    breaker.opened_at = Instant::now();
+                }
+            } else {
+                breaker.consecutive_failures = 0;
+                breaker.state = CircuitState::Closed;
+            }
+            breaker.trial_started = None;
+
+            (previous != breaker.state).then_some((previous, breaker.state))
+        };
+
+        self.notify(change);
+    }
+
+    fn notify(&self, change: Option<(CircuitState, CircuitState)>) {
+        if let (Some((from, to)), Some(listener)) = (change, &self.listener) {
+            listener(from, to);
+        }
+    }
+}
+
+impl fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("failure_threshold", &self.failure_threshold)
+            .field("cooldown", &self.cooldown)
+            .field("state", &self.state())
+            .finish()
+    }
+}
+
+/// Whether an error means the API could not be reached
+fn is_transport_failure(error: &WusulError) -> bool {
+    matches!(
+        error,
+        WusulError::HttpError(_)
+            | WusulError::ConnectError(_)
+            | WusulError::TlsError(_)
+            | WusulError::BodyError(_)
+            | WusulError::Timeout
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::{HeaderMap, StatusCode};
+
+    fn response(status: u16) -> Result<HttpResponse> {
+        Ok(HttpResponse {
+            status: StatusCode::from_u16(status).unwrap(),
+            headers: HeaderMap::new(),
+            body: String::new(),
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(30));
+
+        breaker.record(&response(503));
+        breaker.record(&Err(WusulError::Timeout));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.record(&response(500));
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(
+            breaker.admit(),
+            Err(WusulError::CircuitOpen { retry_after }) if retry_after == Duration::from_secs(30)
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_success_resets_the_count() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(30));
+
+        breaker.record(&response(503));
+        breaker.record(&response(404));
+        breaker.record(&response(503));
+
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_client_errors_are_not_failures() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(30));
+
+        breaker.record(&response(400));
+        breaker.record(&Err(WusulError::InvalidParameter("bad".to_string())));
+
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_half_open_after_cooldown() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(30));
+        breaker.record(&response(503));
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert!(breaker.admit().is_ok());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // Only one trial at a time
+        assert!(matches!(
+            breaker.admit(),
+            Err(WusulError::CircuitOpen { .. })
+        ));
+
+        breaker.record(&response(200));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.admit().is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_trial_reopens() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(30));
+        breaker.record(&response(503));
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        breaker.admit().unwrap();
+        breaker.record(&Err(WusulError::Timeout));
+
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.admit().is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_state_changes_are_reported() {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&changes);
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(30))
+            .on_state_change(move |from, to| recorded.lock().unwrap().push((from, to)));
+
+        breaker.record(&response(503));
+        tokio::time::advance(Duration::from_secs(30)).await;
+        breaker.admit().unwrap();
+        breaker.record(&response(200));
+
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![
+                (CircuitState::Closed, CircuitState::Open),
+                (CircuitState::Open, CircuitState::HalfOpen),
+                (CircuitState::HalfOpen, CircuitState::Closed),
+            ]
+        );
+    }
+}
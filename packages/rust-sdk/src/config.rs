@@ -0,0 +1,344 @@
+//! Loading [`WusulConfig`] from the environment and from credentials files.
+//!
+//! [`WusulConfig::from_env`] reads the settings from environment variables:
+//!
+//! | Variable              | Setting                                 |
+//! |-----------------------|-----------------------------------------|
+//! | `WUSUL_ACCOUNT_ID`    | Account ID (required)                   |
+//! | `WUSUL_SHARED_SECRET` | Shared secret (required)                |
+//! | `WUSUL_BASE_URL`      | API base URL (optional)                 |
+//! | `WUSUL_TIMEOUT`       | Request timeout in seconds (optional)   |
+//!
+//! [`WusulConfig::from_profile`] reads a named profile from a TOML
+//! credentials file, `~/.wusul/credentials` unless `WUSUL_CREDENTIALS_FILE`
+//! points elsewhere:
+//!
+//! ```toml
+//! [staging]
+//! account_id = "0xabc1234567"
+//! shared_secret = "staging_secret"
+//! base_url = "https://api.staging.wusul.io"
+//!
+//! [prod]
+//! account_id = "0xdef7654321"
+//! shared_secret = "prod_secret"
+//! timeout = 10
+//! ```
+//!
+//! Settings that are not given keep the defaults of [`WusulConfig::new`].
+
+use crate::error::{Result, WusulError};
+use crate::secret::SecretString;
+use crate::types::WusulConfig;
+use serde::Deserialize;
+use std::env::{self, VarError};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+/// Environment variable holding the account ID
+pub const ACCOUNT_ID_VAR: &str = "WUSUL_ACCOUNT_ID";
+/// Environment variable holding the shared secret
+pub const SHARED_SECRET_VAR: &str = "WUSUL_SHARED_SECRET";
+/// Environment variable holding the API base URL
+pub const BASE_URL_VAR: &str = "WUSUL_BASE_URL";
+/// Environment variable holding the request timeout in seconds
+pub const TIMEOUT_VAR: &str = "WUSUL_TIMEOUT";
+/// Environment variable overriding the location of the credentials file
+pub const CREDENTIALS_FILE_VAR: &str = "WUSUL_CREDENTIALS_FILE";
+
+/// One profile of a credentials file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Profile {
+    account_id: String,
+    shared_secret: String,
+    base_url: Option<String>,
+    timeout: Option<f64>,
+}
+
+impl WusulConfig {
+    /// Load the configuration from `WUSUL_*` environment variables
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WusulError::ConfigError`] if `WUSUL_ACCOUNT_ID` or
+    /// `WUSUL_SHARED_SECRET` is missing or empty, if the shared secret has
+    /// leading or trailing whitespace, or if a variable is not valid Unicode
+    /// or `WUSUL_TIMEOUT` is not a positive number of seconds.
+    pub fn from_env() -> Result<Self> {
+        Self::from_vars(|name| match env::var(name) {
+            Ok(value) => Ok(Some(value)),
+            Err(VarError::NotPresent) => Ok(None),
+            Err(VarError::NotUnicode(_)) => Err(WusulError::ConfigError(format!(
+                "{} is not valid Unicode",
+                name
+            ))),
+        })
+    }
+
+    /// Load the named profile from the credentials file
+    ///
+    /// The file is `WUSUL_CREDENTIALS_FILE` if set, `~/.wusul/credentials`
+    /// otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WusulError::ConfigError`] if the file cannot be read or
+    /// parsed, or if the profile is missing or invalid.
+    pub fn from_profile(name: &str) -> Result<Self> {
+        Self::from_profile_file(credentials_file()?, name)
+    }
+
+    /// Load the named profile from the credentials file at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WusulError::ConfigError`] if the file cannot be read or
+    /// parsed, or if the profile is missing or invalid.
+    pub fn from_profile_file(path: impl AsRef<Path>, name: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = Zeroizing::new(std::fs::read_to_string(path).map_err(|err| {
+            WusulError::ConfigError(format!(
+                "Failed to read credentials file {}: {}",
+                path.display(),
+                err
+            ))
+        })?);
+
+        let mut profiles: toml::Table = contents.parse().map_err(|err| {
+            WusulError::ConfigError(format!(
+                "Invalid credentials file {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+
+        let profile = profiles.remove(name).ok_or_else(|| {
+            let mut available: Vec<&str> = profiles
+                .iter()
+                .filter(|(_, value)| value.is_table())
+                .map(|(name, _)| name.as_str())
+                .collect();
+            available.sort_unstable();
+            WusulError::ConfigError(format!(
+                "Profile '{}' not found in {} (available: {})",
+                name,
+                path.display(),
+                if available.is_empty() {
+                    "none".to_string()
+                } else {
+                    available.join(", ")
+                }
+            ))
+        })?;
+        let profile: Profile = profile.try_into().map_err(|err| {
+            WusulError::ConfigError(format!(
+                "Invalid profile '{}' in {}: {}",
+                name,
+                path.display(),
+                err
+            ))
+        })?;
+
+        let context = |field: &str| format!("{} of profile '{}'", field, name);
+        let mut config = WusulConfig::new(
+            required(profile.account_id, &context("account_id"))?,
+            String::new(),
+        );
+        config.shared_secret = required_secret(profile.shared_secret, &context("shared_secret"))?;
+        if let Some(base_url) = profile.base_url {
+            config.base_url = required(base_url, &context("base_url"))?;
+        }
+        if let Some(timeout) = profile.timeout {
+            config.timeout = seconds(timeout, &context("timeout"), &timeout.to_string())?;
+        }
+        Ok(config)
+    }
+
+    /// Build the configuration from variables looked up by `lookup`
+    fn from_vars<F>(lookup: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Result<Option<String>>,
+    {
+        let require = |name: &str| {
+            lookup(name)?.ok_or_else(|| WusulError::ConfigError(format!("{} is not set", name)))
+        };
+
+        let mut config = WusulConfig::new(
+            required(require(ACCOUNT_ID_VAR)?, ACCOUNT_ID_VAR)?,
+            String::new(),
+        );
+        config.shared_secret = required_secret(require(SHARED_SECRET_VAR)?, SHARED_SECRET_VAR)?;
+        if let Some(base_url) = lookup(BASE_URL_VAR)? {
+            config.base_url = required(base_url, BASE_URL_VAR)?;
+        }
+        if let Some(timeout) = lookup(TIMEOUT_VAR)? {
+            let parsed = timeout.trim().parse::<f64>().map_err(|_| {
+                WusulError::ConfigError(format!(
+                    "{} must be a positive number of seconds, got '{}'",
+                    TIMEOUT_VAR, timeout
+                ))
+            })?;
+            config.timeout = seconds(parsed, TIMEOUT_VAR, &timeout)?;
+        }
+        Ok(config)
+    }
+}
+
+/// Trim surrounding whitespace and reject empty values
+fn required(value: String, what: &str) -> Result<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(WusulError::ConfigError(format!("{} is empty", what)));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Reject empty shared secrets and secrets with surrounding whitespace
+///
+/// The secret is not trimmed: signing with anything but the exact secret
+/// fails with a 401 that does not point at the configuration.
+fn required_secret(value: String, what: &str) -> Result<SecretString> {
+    let secret = SecretString::new(value);
+    let value = secret.expose_secret();
+    if value.trim().is_empty() {
+        return Err(WusulError::ConfigError(format!("{} is empty", what)));
+    }
+    if value.trim() != value {
+        return Err(WusulError::ConfigError(format!(
+            "{} has leading or trailing whitespace",
+            what
+        )));
+    }
+    Ok(secret)
+}
+
+/// Convert a number of seconds to a timeout
+fn seconds(value: f64, what: &str, raw: &str) -> Result<Duration> {
+    Duration::try_from_secs_f64(value)
+        .ok()
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| {
+            WusulError::ConfigError(format!(
+                "{} must be a positive number of seconds, got '{}'",
+                what, raw
+            ))
+        })
+}
+
+/// Location of the credentials file
+fn credentials_file() -> Result<PathBuf> {
+    if let Some(path) = env::var_os(CREDENTIALS_FILE_VAR).filter(|path| !path.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(|home| PathBuf::from(home).join(".wusul").join("credentials"))
+        .ok_or_else(|| {
+            WusulError::ConfigError(format!(
+                "Cannot locate the credentials file: no home directory; set {}",
+                CREDENTIALS_FILE_VAR
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn from_vars(vars: &[(&str, &str)]) -> Result<WusulConfig> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        WusulConfig::from_vars(|name| Ok(vars.get(name).cloned()))
+    }
+
+    fn config_error(result: Result<WusulConfig>) -> String {
+        match result {
+            Err(WusulError::ConfigError(msg)) => msg,
+            other => panic!("Expected ConfigError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_vars() {
+        let config = from_vars(&[
+            (ACCOUNT_ID_VAR, "0xabc1234567"),
+            (SHARED_SECRET_VAR, "secret"),
+            (BASE_URL_VAR, "https://api.staging.wusul.io"),
+            (TIMEOUT_VAR, "2.5"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.account_id, "0xabc1234567");
+        assert_eq!(config.shared_secret.expose_secret(), "secret");
+        assert_eq!(config.base_url, "https://api.staging.wusul.io");
+        assert_eq!(config.timeout, Duration::from_millis(2500));
+    }
+
+    #[test]
+    fn test_from_vars_defaults() {
+        let config = from_vars(&[
+            (ACCOUNT_ID_VAR, "0xabc1234567"),
+            (SHARED_SECRET_VAR, "secret"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.base_url, "https://api.wusul.io");
+        assert_eq!(config.timeout, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_from_vars_missing_values() {
+        let msg = config_error(from_vars(&[(SHARED_SECRET_VAR, "secret")]));
+        assert_eq!(msg, "WUSUL_ACCOUNT_ID is not set");
+
+        let msg = config_error(from_vars(&[
+            (ACCOUNT_ID_VAR, "0xabc1234567"),
+            (SHARED_SECRET_VAR, "  "),
+        ]));
+        assert_eq!(msg, "WUSUL_SHARED_SECRET is empty");
+    }
+
+    #[test]
+    fn test_from_vars_whitespace() {
+        let config = from_vars(&[
+            (ACCOUNT_ID_VAR, " 0xabc1234567\n"),
+            (SHARED_SECRET_VAR, "secret"),
+        ])
+        .unwrap();
+        assert_eq!(config.account_id, "0xabc1234567");
+
+        let msg = config_error(from_vars(&[
+            (ACCOUNT_ID_VAR, "0xabc1234567"),
+            (SHARED_SECRET_VAR, "secret\n"),
+        ]));
+        assert_eq!(
+            msg,
+            "WUSUL_SHARED_SECRET has leading or trailing whitespace"
+        );
+    }
+
+    #[test]
+    fn test_from_vars_malformed_timeout() {
+        for timeout in ["soon", "0", "-5", "NaN"] {
+            let msg = config_error(from_vars(&[
+                (ACCOUNT_ID_VAR, "0xabc1234567"),
+                (SHARED_SECRET_VAR, "secret"),
+                (TIMEOUT_VAR, timeout),
+            ]));
+            assert_eq!(
+                msg,
+                format!(
+                    "WUSUL_TIMEOUT must be a positive number of seconds, got '{}'",
+                    timeout
+                )
+            );
+        }
+    }
+}
@@ -1,18 +1,255 @@
+use crate::retry::RetryPolicy;
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
+use std::fmt;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Result type for Wusul SDK operations
 pub type Result<T> = std::result::Result<T, WusulError>;
 
+/// Machine-readable error code returned by the Wusul API
+///
+/// Codes the SDK does not know yet are kept verbatim in
+/// [`Unknown`](Self::Unknown), so new server codes never fail to decode.
+/// Compare against the string form with `==` when matching on a code this
+/// enum does not list.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// `VALIDATION_ERROR`: the request failed validation; see [`ErrorBody::field_errors`]
+    ValidationError,
+    /// `UNAUTHORIZED`: missing or invalid credentials or signature
+    Unauthorized,
+    /// `FORBIDDEN`: the account may not perform this operation
+    Forbidden,
+    /// `NOT_FOUND`: the route or record does not exist
+    NotFound,
+    /// `ACCESS_PASS_NOT_FOUND`
+    AccessPassNotFound,
+    /// `CARD_TEMPLATE_NOT_FOUND`
+    CardTemplateNotFound,
+    /// `CARD_TEMPLATE_NOT_PUBLISHED`: passes can only be issued from published templates
+    CardTemplateNotPublished,
+    /// `DUPLICATE_ENTRY`: a record with the same unique field already exists
+    DuplicateEntry,
+    /// `INVALID_REFERENCE`: a referenced record does not exist
+    InvalidReference,
+    /// `RATE_LIMIT_EXCEEDED`
+    RateLimitExceeded,
+    /// `WEBHOOK_RATE_LIMIT_EXCEEDED`
+    WebhookRateLimitExceeded,
+    /// `WALLET_ERROR`: Apple or Google Wallet rejected the operation
+    WalletError,
+    /// `IDEMPOTENCY_KEY_IN_USE`: a request with the same idempotency key is still being processed
+    IdempotencyKeyInUse,
+    /// `IDEMPOTENCY_KEY_REUSED`: the idempotency key was already used for a different request
+    IdempotencyKeyReused,
+    /// `INTERNAL_ERROR`
+    InternalError,
+    /// Any other code, including `UNKNOWN_ERROR` for responses without a Wusul error body
+    Unknown(String),
+}
+
+impl ErrorCode {
+    /// The code as sent by the server
+    pub fn as_str(&self) -> &str {
+        match self {
+            ErrorCode::ValidationError => "VALIDATION_ERROR",
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::AccessPassNotFound => "ACCESS_PASS_NOT_FOUND",
+            ErrorCode::CardTemplateNotFound => "CARD_TEMPLATE_NOT_FOUND",
+            ErrorCode::CardTemplateNotPublished => "CARD_TEMPLATE_NOT_PUBLISHED",
+            ErrorCode::DuplicateEntry => "DUPLICATE_ENTRY",
+            ErrorCode::InvalidReference => "INVALID_REFERENCE",
+            ErrorCode::RateLimitExceeded => "RATE_LIMIT_EXCEEDED",
+            ErrorCode::WebhookRateLimitExceeded => "WEBHOOK_RATE_LIMIT_EXCEEDED",
+            ErrorCode::WalletError => "WALLET_ERROR",
+            ErrorCode::IdempotencyKeyInUse => "IDEMPOTENCY_KEY_IN_USE",
+            ErrorCode::IdempotencyKeyReused => "IDEMPOTENCY_KEY_REUSED",
+            ErrorCode::InternalError => "INTERNAL_ERROR",
+            ErrorCode::Unknown(code) => code,
+        }
+    }
+
+    /// Whether the code means the requested record or route does not exist
+    pub fn is_not_found(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::NotFound | ErrorCode::AccessPassNotFound | ErrorCode::CardTemplateNotFound
+        )
+    }
+}
+
+impl From<&str> for ErrorCode {
+    fn from(code: &str) -> Self {
+        match code {
+            "VALIDATION_ERROR" => ErrorCode::ValidationError,
+            "UNAUTHORIZED" => ErrorCode::Unauthorized,
+            "FORBIDDEN" => ErrorCode::Forbidden,
+            "NOT_FOUND" => ErrorCode::NotFound,
+            "ACCESS_PASS_NOT_FOUND" => ErrorCode::AccessPassNotFound,
+            "CARD_TEMPLATE_NOT_FOUND" => ErrorCode::CardTemplateNotFound,
+            "CARD_TEMPLATE_NOT_PUBLISHED" => ErrorCode::CardTemplateNotPublished,
+            "DUPLICATE_ENTRY" => ErrorCode::DuplicateEntry,
+            "INVALID_REFERENCE" => ErrorCode::InvalidReference,
+            "RATE_LIMIT_EXCEEDED" => ErrorCode::RateLimitExceeded,
+            "WEBHOOK_RATE_LIMIT_EXCEEDED" => ErrorCode::WebhookRateLimitExceeded,
+            "WALLET_ERROR" => ErrorCode::WalletError,
+            "IDEMPOTENCY_KEY_IN_USE" => ErrorCode::IdempotencyKeyInUse,
+            "IDEMPOTENCY_KEY_REUSED" => ErrorCode::IdempotencyKeyReused,
+            "INTERNAL_ERROR" => ErrorCode::InternalError,
+            other => ErrorCode::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for ErrorCode {
+    fn from(code: String) -> Self {
+        match ErrorCode::from(code.as_str()) {
+            ErrorCode::Unknown(_) => ErrorCode::Unknown(code),
+            known => known,
+        }
+    }
+}
+
+impl From<ErrorCode> for String {
+    fn from(code: ErrorCode) -> Self {
+        match code {
+            ErrorCode::Unknown(code) => code,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl PartialEq<str> for ErrorCode {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ErrorCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A validation failure of a single request field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// Dotted path of the offending field, e.g. `metadata.employee_id`
+    #[serde(alias = "field")]
+    pub path: String,
+    /// Why the field was rejected
+    pub message: String,
+}
+
+/// Error object returned by the Wusul API in the `error` field of the response envelope
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorBody {
+    /// Machine-readable error code, e.g. `VALIDATION_ERROR`
+    pub code: ErrorCode,
+    /// Human-readable error message; empty if the server sent none
+    #[serde(default)]
+    pub message: String,
+    /// Additional error details, e.g. the offending fields of a validation error
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl ErrorBody {
+    /// Build an error body for responses that did not carry a Wusul error envelope
+    pub(crate) fn unknown(message: String) -> Self {
+        Self {
+            code: ErrorCode::Unknown("UNKNOWN_ERROR".to_string()),
+            message,
+            details: None,
+        }
+    }
+
+    /// The offending fields listed in `details`, empty unless this is a validation error
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        match &self.details {
+            Some(serde_json::Value::Array(details)) => details
+                .iter()
+                .filter_map(|detail| FieldError::deserialize(detail).ok())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for ErrorBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+/// A failure reported by the HTTP stack
+///
+/// Wraps the underlying error without naming its type, so the HTTP stack can
+/// change without breaking callers. Walk [`source`](StdError::source) for the
+/// details, such as the `std::io::Error` of a refused connection.
+#[derive(Debug)]
+pub struct TransportError(Box<dyn StdError + Send + Sync>);
+
+impl TransportError {
+    /// Wrap an error raised while sending a request or reading its response
+    pub fn new(error: impl Into<Box<dyn StdError + Send + Sync>>) -> Self {
+        Self(error.into())
+    }
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl StdError for TransportError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&*self.0)
+    }
+}
+
 /// Errors that can occur when using the Wusul SDK
 #[derive(Error, Debug)]
 pub enum WusulError {
-    /// HTTP request failed
+    /// HTTP request failed for a reason not covered by a more specific variant
     #[error("HTTP request failed: {0}")]
-    HttpError(#[from] reqwest::Error),
+    HttpError(#[source] TransportError),
+
+    /// The API could not be reached: DNS lookup failed, or the connection was refused or reset
+    #[error("Connection failed: {0}")]
+    ConnectError(#[source] TransportError),
+
+    /// The TLS handshake failed, e.g. because the server certificate is not trusted
+    #[error("TLS error: {0}")]
+    TlsError(#[source] TransportError),
+
+    /// The response body could not be read or decompressed
+    #[error("Failed to read the response body: {0}")]
+    BodyError(#[source] TransportError),
+
+    /// The response body exceeded the configured maximum size
+    #[error("Response body exceeds the limit of {limit} bytes")]
+    ResponseTooLarge {
+        /// The limit set with [`WusulConfig::with_max_response_size`](crate::WusulConfig::with_max_response_size)
+        limit: usize,
+    },
 
     /// API returned an error response
-    #[error("API error: {status} - {message}")]
-    ApiError { status: u16, message: String },
+    #[error("API error: {status} - {error}")]
+    ApiError { status: u16, error: ErrorBody },
 
     /// Failed to serialize/deserialize data
     #[error("Serialization error: {0}")]
@@ -24,7 +261,7 @@ pub enum WusulError {
 
     /// Authentication failed
     #[error("Authentication error: {0}")]
-    AuthError(String),
+    AuthError(ErrorBody),
 
     /// Invalid parameter provided
     #[error("Invalid parameter: {0}")]
@@ -32,13 +269,224 @@ pub enum WusulError {
 
     /// Resource not found
     #[error("Resource not found: {0}")]
-    NotFound(String),
+    NotFound(ErrorBody),
 
     /// Rate limit exceeded
-    #[error("Rate limit exceeded")]
-    RateLimitExceeded,
+    #[error("Rate limit exceeded: {error}")]
+    RateLimitExceeded {
+        error: ErrorBody,
+        /// How long the server asked to wait before retrying, from `Retry-After` or `RateLimit-Reset`
+        retry_after: Option<Duration>,
+    },
 
-    /// Timeout error
+    /// The request timed out, or the server answered 408 Request Timeout
     #[error("Request timeout")]
     Timeout,
+
+    /// The circuit breaker is open after repeated failures; the request was not sent
+    #[error("Circuit breaker open; requests fail fast for another {retry_after:?}")]
+    CircuitOpen {
+        /// Time left until the breaker lets a trial request through
+        retry_after: Duration,
+    },
+
+    /// A `POST` sent with an SDK-generated idempotency key failed after it may
+    /// have reached the API
+    ///
+    /// Retry the call with `idempotency_key` in its
+    /// [`RequestOptions`](crate::RequestOptions): if the API already performed
+    /// the operation, it returns the original result instead of repeating it.
+    #[error("{source} (retry with idempotency key {idempotency_key})")]
+    OutcomeUnknown {
+        /// The key sent with every attempt of the request
+        idempotency_key: String,
+        /// The error of the last attempt
+        #[source]
+        source: Box<WusulError>,
+    },
+}
+
+impl WusulError {
+    /// The error body returned by the server, if this error came from an API response
+    pub fn error_body(&self) -> Option<&ErrorBody> {
+        match self {
+            WusulError::ApiError { error, .. } | WusulError::RateLimitExceeded { error, .. } => {
+                Some(error)
+            }
+            WusulError::AuthError(error) | WusulError::NotFound(error) => Some(error),
+            WusulError::OutcomeUnknown { source, .. } => source.error_body(),
+            _ => None,
+        }
+    }
+
+    /// The idempotency key to retry the request with, if its outcome is unknown
+    ///
+    /// See [`OutcomeUnknown`](Self::OutcomeUnknown).
+    pub fn idempotency_key(&self) -> Option<&str> {
+        match self {
+            WusulError::OutcomeUnknown {
+                idempotency_key, ..
+            } => Some(idempotency_key),
+            _ => None,
+        }
+    }
+
+    /// The server's machine-readable error code, if any
+    ///
+    /// Codes such as `NOT_FOUND` are stable and never localized, so branch on
+    /// them rather than on the message.
+    pub fn code(&self) -> Option<&str> {
+        self.error_body().map(|e| e.code.as_str())
+    }
+
+    /// The server's error code, typed
+    pub fn error_code(&self) -> Option<&ErrorCode> {
+        self.error_body().map(|e| &e.code)
+    }
+
+    /// The fields rejected by a validation error, to show next to form inputs
+    ///
+    /// Empty for other errors.
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        self.error_body()
+            .map(ErrorBody::field_errors)
+            .unwrap_or_default()
+    }
+
+    /// Whether repeating the request later may succeed
+    ///
+    /// True for timeouts, connection failures, rate limiting and server
+    /// errors, the errors the default [`RetryPolicy`] retries. The client has
+    /// already retried these before returning them, so this is for deciding
+    /// whether to re-queue work, not for retrying immediately.
+    pub fn is_retryable(&self) -> bool {
+        RetryPolicy::default().is_retryable(self)
+    }
+
+    /// Whether the request was rejected because of what the caller sent
+    ///
+    /// True for 4xx responses and for parameters the SDK rejected before
+    /// sending. Such requests fail again unless they are changed, rate
+    /// limiting aside.
+    pub fn is_client_error(&self) -> bool {
+        match self {
+            WusulError::ApiError { status, .. } => (400..500).contains(status),
+            WusulError::AuthError(_)
+            | WusulError::NotFound(_)
+            | WusulError::RateLimitExceeded { .. }
+            | WusulError::InvalidParameter(_) => true,
+            WusulError::OutcomeUnknown { source, .. } => source.is_client_error(),
+            _ => false,
+        }
+    }
+
+    /// The server's error message, if any, exactly as the API sent it
+    ///
+    /// The SDK sends the language requested with [`WusulConfig::with_locale`]
+    /// or a per-request `Accept-Language`, but does not translate the message
+    /// itself: it is only localized if the API honours that header, and the
+    /// Wusul API currently answers in English.
+    ///
+    /// [`WusulConfig::with_locale`]: crate::WusulConfig::with_locale
+    pub fn server_message(&self) -> Option<&str> {
+        self.error_body().map(|e| e.message.as_str())
+    }
+
+    /// The server's error details, if any
+    pub fn details(&self) -> Option<&serde_json::Value> {
+        self.error_body().and_then(|e| e.details.as_ref())
+    }
+
+    /// How long to wait before retrying: the delay the server asked for on a rate
+    /// limit error, or the time until an open circuit breaker half-opens
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            WusulError::RateLimitExceeded { retry_after, .. } => *retry_after,
+            WusulError::CircuitOpen { retry_after } => Some(*retry_after),
+            WusulError::OutcomeUnknown { source, .. } => source.retry_after(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_error_code_round_trip() {
+        let body: ErrorBody = serde_json::from_value(json!({
+            "code": "CARD_TEMPLATE_NOT_PUBLISHED",
+            "message": "Card template must be published before issuing passes"
+        }))
+        .unwrap();
+        assert_eq!(body.code, ErrorCode::CardTemplateNotPublished);
+
+        let body: ErrorBody = serde_json::from_value(json!({
+            "code": "PASS_LIMIT_REACHED",
+            "message": "Pass limit reached"
+        }))
+        .unwrap();
+        assert_eq!(
+            body.code,
+            ErrorCode::Unknown("PASS_LIMIT_REACHED".to_string())
+        );
+        assert_eq!(body.code, "PASS_LIMIT_REACHED");
+        assert_eq!(
+            serde_json::to_value(&body).unwrap()["code"],
+            "PASS_LIMIT_REACHED"
+        );
+    }
+
+    #[test]
+    fn test_field_errors() {
+        let body: ErrorBody = serde_json::from_value(json!({
+            "code": "VALIDATION_ERROR",
+            "message": "Request validation failed",
+            "details": [
+                {"field": "full_name", "message": "Required"},
+                {"field": "metadata.employee_id", "message": "Expected string"}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            body.field_errors(),
+            vec![
+                FieldError {
+                    path: "full_name".to_string(),
+                    message: "Required".to_string(),
+                },
+                FieldError {
+                    path: "metadata.employee_id".to_string(),
+                    message: "Expected string".to_string(),
+                },
+            ]
+        );
+        assert!(ErrorBody::unknown("Bad Gateway".to_string())
+            .field_errors()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_classification() {
+        let error = |status: u16| WusulError::ApiError {
+            status,
+            error: ErrorBody::unknown("error".to_string()),
+        };
+
+        assert!(error(400).is_client_error());
+        assert!(!error(400).is_retryable());
+        assert!(!error(503).is_client_error());
+        assert!(error(503).is_retryable());
+        assert!(WusulError::Timeout.is_retryable());
+
+        let rate_limited = WusulError::RateLimitExceeded {
+            error: ErrorBody::unknown("Too many requests".to_string()),
+            retry_after: None,
+        };
+        assert!(rate_limited.is_client_error());
+        assert!(rate_limited.is_retryable());
+    }
 }
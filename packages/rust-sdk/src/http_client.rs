@@ -1,36 +1,198 @@
-use crate::auth::{create_auth_headers, create_get_auth_headers};
-use crate::error::{Result, WusulError};
-use reqwest::{Client, Response, StatusCode};
+use crate::auth::{
+    canonical_body, create_auth_headers, create_get_auth_headers, create_signature_v2,
+    SignatureVersion, NONCE_HEADER, SIGNATURE_HEADER, SIGNATURE_VERSION_HEADER, TIMESTAMP_HEADER,
+};
+use crate::circuit_breaker::CircuitBreaker;
+use crate::error::{ErrorBody, Result, WusulError};
+use crate::middleware::{Middleware, Next};
+use crate::rate_limit::RateLimiter;
+use crate::retry::{
+    generate_idempotency_key, is_replayable, retry_after, with_idempotency_key, RetryPolicy,
+    IDEMPOTENCY_KEY_HEADER,
+};
+use crate::secret::{SecretProvider, SecretString};
+#[cfg(feature = "tracing")]
+use crate::telemetry::{Operation, TraceContextSource};
+use crate::transport::{
+    HeaderValue, HttpRequest, HttpResponse, Method, ReqwestTransport, StatusCode, Transport,
+};
+use crate::types::{
+    ApiResult, RequestOptions, ResponseEnvelope, ResponseMeta, WithMetadata, WusulConfig,
+};
+use http::header::ACCEPT_LANGUAGE;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use serde::de::DeserializeOwned;
+use std::sync::Arc;
 use std::time::Duration;
 
+/// Characters escaped in a path segment: all but the unreserved characters of RFC 3986
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// An API operation: its name, HTTP method and path template
+///
+/// Used to label instrumentation; the template keeps IDs out of span names.
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+pub(crate) struct Endpoint<'a> {
+    pub operation: &'static str,
+    pub method: Method,
+    pub path_template: &'a str,
+}
+
+impl<'a> Endpoint<'a> {
+    pub const fn new(operation: &'static str, method: Method, path_template: &'a str) -> Self {
+        Self {
+            operation,
+            method,
+            path_template,
+        }
+    }
+}
+
 /// HTTP client for making authenticated requests to the Wusul API
 pub struct HttpClient {
-    client: Client,
+    transport: Arc<dyn Transport>,
+    middleware: Vec<Arc<dyn Middleware>>,
     account_id: String,
-    shared_secret: String,
+    secret: Arc<dyn SecretProvider>,
     base_url: String,
+    retry_policy: RetryPolicy,
+    signature_version: SignatureVersion,
+    locale: Option<HeaderValue>,
+    rate_limiter: Option<RateLimiter>,
+    circuit_breaker: Option<CircuitBreaker>,
+    #[cfg(feature = "tracing")]
+    trace_context: Option<Arc<dyn TraceContextSource>>,
 }
 
 impl HttpClient {
-    /// Create a new HTTP client
+    /// Create a new HTTP client using the default `reqwest` transport
     pub fn new(
         account_id: String,
         shared_secret: String,
         base_url: String,
         timeout: Duration,
     ) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(timeout)
-            .build()
-            .map_err(WusulError::HttpError)?;
-
-        Ok(Self {
-            client,
+        let transport = ReqwestTransport::new(timeout)?;
+        Ok(Self::with_transport(
             account_id,
             shared_secret,
             base_url,
-        })
+            Arc::new(transport),
+        ))
+    }
+
+    /// Create a new HTTP client from a client configuration
+    ///
+    /// Unless `config` provides a custom transport, the default `reqwest`
+    /// transport is built with its timeout, proxy and TLS settings.
+    pub fn from_config(config: WusulConfig) -> Result<Self> {
+        let transport: Arc<dyn Transport> = match &config.transport {
+            Some(transport) => Arc::clone(transport),
+            None => Arc::new(ReqwestTransport::from_config(&config)?),
+        };
+
+        let secret = config
+            .secret_provider
+            .unwrap_or_else(|| Arc::new(config.shared_secret));
+        let mut http =
+            Self::with_transport(config.account_id, String::new(), config.base_url, transport)
+                .with_secret_provider(secret)
+                .with_retry_policy(config.retry_policy)
+                .with_signature_version(config.signature_version)
+                .with_middleware(config.middleware);
+        if let Some(locale) = &config.locale {
+            let locale = HeaderValue::from_str(locale)
+                .map_err(|_| WusulError::ConfigError(format!("Invalid locale: {:?}", locale)))?;
+            http = http.with_locale(locale);
+        }
+        if let Some(rate_limiter) = config.rate_limiter {
+            http = http.with_rate_limiter(rate_limiter);
+        }
+        if let Some(circuit_breaker) = config.circuit_breaker {
+            http = http.with_circuit_breaker(circuit_breaker);
+        }
+        #[cfg(feature = "tracing")]
+        if let Some(source) = config.trace_context {
+            http = http.with_trace_context(source);
+        }
+        Ok(http)
+    }
+
+    /// Create a new HTTP client that sends requests through `transport`
+    pub fn with_transport(
+        account_id: String,
+        shared_secret: String,
+        base_url: String,
+        transport: Arc<dyn Transport>,
+    ) -> Self {
+        Self {
+            transport,
+            middleware: Vec::new(),
+            account_id,
+            secret: Arc::new(SecretString::new(shared_secret)),
+            base_url,
+            retry_policy: RetryPolicy::default(),
+            signature_version: SignatureVersion::default(),
+            locale: None,
+            rate_limiter: None,
+            circuit_breaker: None,
+            #[cfg(feature = "tracing")]
+            trace_context: None,
+        }
+    }
+
+    /// Take the shared secret from `provider`, asked on every request
+    pub fn with_secret_provider(mut self, provider: Arc<dyn SecretProvider>) -> Self {
+        self.secret = provider;
+        self
+    }
+
+    /// Set the policy used to retry failed requests
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sign requests with `version` of the signing scheme
+    pub fn with_signature_version(mut self, version: SignatureVersion) -> Self {
+        self.signature_version = version;
+        self
+    }
+
+    /// Send `locale` as the `Accept-Language` of requests that do not set one
+    pub fn with_locale(mut self, locale: HeaderValue) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Hold every request attempt until `rate_limiter` has budget for it
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Fail fast through `circuit_breaker` while the API keeps failing
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Continue the trace reported by `source` in the `traceparent` header
+    #[cfg(feature = "tracing")]
+    pub fn with_trace_context(mut self, source: Arc<dyn TraceContextSource>) -> Self {
+        self.trace_context = Some(source);
+        self
+    }
+
+    /// Set the middleware that wraps every request attempt, outermost first
+    pub fn with_middleware(mut self, middleware: Vec<Arc<dyn Middleware>>) -> Self {
+        self.middleware = middleware;
+        self
     }
 
     /// Make a GET request
@@ -39,36 +201,21 @@ impl HttpClient {
         path: &str,
         query_params: Option<&serde_json::Value>,
     ) -> Result<T> {
-        let url = format!("{}{}", self.base_url, path);
-        let (account_id, signature) =
-            create_get_auth_headers(&self.account_id, &self.shared_secret, query_params);
-
-        let mut request = self
-            .client
-            .get(&url)
-            .header("X-ACCT-ID", account_id)
-            .header("X-PAYLOAD-SIG", signature)
-            .header("Content-Type", "application/json");
-
-        // Add query parameters if provided
-        if let Some(params) = query_params {
-            if let Some(obj) = params.as_object() {
-                for (key, value) in obj {
-                    if let Some(v) = value.as_str() {
-                        request = request.query(&[(key, v)]);
-                    } else if let Some(v) = value.as_i64() {
-                        request = request.query(&[(key, v.to_string())]);
-                    } else if let Some(v) = value.as_u64() {
-                        request = request.query(&[(key, v.to_string())]);
-                    } else if let Some(v) = value.as_bool() {
-                        request = request.query(&[(key, v.to_string())]);
-                    }
-                }
-            }
-        }
+        self.get_with_metadata(path, query_params)
+            .await
+            .map(|response| response.data)
+    }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+    /// Make a GET request and return the response metadata alongside the data
+    pub async fn get_with_metadata<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query_params: Option<&serde_json::Value>,
+    ) -> Result<WithMetadata<T>> {
+        let endpoint = Endpoint::new("http_client.get", Method::GET, path);
+        self.request(endpoint, path, query_params, &RequestOptions::default())
+            .await
+            .map(WithMetadata::from)
     }
 
     /// Make a POST request
@@ -77,25 +224,21 @@ impl HttpClient {
         path: &str,
         data: Option<&serde_json::Value>,
     ) -> Result<T> {
-        let url = format!("{}{}", self.base_url, path);
-        let (account_id, signature) =
-            create_auth_headers(&self.account_id, &self.shared_secret, data);
-
-        let request = self
-            .client
-            .post(&url)
-            .header("X-ACCT-ID", account_id)
-            .header("X-PAYLOAD-SIG", signature)
-            .header("Content-Type", "application/json");
-
-        let request = if let Some(d) = data {
-            request.json(d)
-        } else {
-            request.json(&serde_json::json!({}))
-        };
+        self.post_with_metadata(path, data)
+            .await
+            .map(|response| response.data)
+    }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+    /// Make a POST request and return the response metadata alongside the data
+    pub async fn post_with_metadata<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        data: Option<&serde_json::Value>,
+    ) -> Result<WithMetadata<T>> {
+        let endpoint = Endpoint::new("http_client.post", Method::POST, path);
+        self.request(endpoint, path, data, &RequestOptions::default())
+            .await
+            .map(WithMetadata::from)
     }
 
     /// Make a PATCH request
@@ -104,78 +247,420 @@ impl HttpClient {
         path: &str,
         data: Option<&serde_json::Value>,
     ) -> Result<T> {
-        let url = format!("{}{}", self.base_url, path);
-        let (account_id, signature) =
-            create_auth_headers(&self.account_id, &self.shared_secret, data);
-
-        let request = self
-            .client
-            .patch(&url)
-            .header("X-ACCT-ID", account_id)
-            .header("X-PAYLOAD-SIG", signature)
-            .header("Content-Type", "application/json");
-
-        let request = if let Some(d) = data {
-            request.json(d)
-        } else {
-            request.json(&serde_json::json!({}))
-        };
+        self.patch_with_metadata(path, data)
+            .await
+            .map(|response| response.data)
+    }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+    /// Make a PATCH request and return the response metadata alongside the data
+    pub async fn patch_with_metadata<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        data: Option<&serde_json::Value>,
+    ) -> Result<WithMetadata<T>> {
+        let endpoint = Endpoint::new("http_client.patch", Method::PATCH, path);
+        self.request(endpoint, path, data, &RequestOptions::default())
+            .await
+            .map(WithMetadata::from)
     }
 
     /// Make a DELETE request
     pub async fn delete<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.delete_with_metadata(path)
+            .await
+            .map(|response| response.data)
+    }
+
+    /// Make a DELETE request and return the response metadata alongside the data
+    pub async fn delete_with_metadata<T: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<WithMetadata<T>> {
+        let endpoint = Endpoint::new("http_client.delete", Method::DELETE, path);
+        self.request(endpoint, path, None, &RequestOptions::default())
+            .await
+            .map(WithMetadata::from)
+    }
+
+    /// Make a signed request with per-call options
+    ///
+    /// GET requests send `payload` as query parameters, POST and PATCH requests
+    /// send it as the JSON body, and DELETE requests ignore it.
+    pub(crate) async fn request<T: DeserializeOwned>(
+        &self,
+        endpoint: Endpoint<'_>,
+        path: &str,
+        payload: Option<&serde_json::Value>,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<T>> {
+        let method = endpoint.method.clone();
         let url = format!("{}{}", self.base_url, path);
-        let (account_id, signature) =
-            create_auth_headers(&self.account_id, &self.shared_secret, None);
-
-        let response = self
-            .client
-            .delete(&url)
-            .header("X-ACCT-ID", account_id)
-            .header("X-PAYLOAD-SIG", signature)
-            .header("Content-Type", "application/json")
-            .send()
-            .await?;
-
-        self.handle_response(response).await
-    }
-
-    /// Handle HTTP response and convert to Result
-    async fn handle_response<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
-        let status = response.status();
-
-        if status.is_success() {
-            let data = response.json::<T>().await?;
-            Ok(data)
-        } else {
-            let status_code = status.as_u16();
-            let error_message = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-
-            Err(match status {
-                StatusCode::NOT_FOUND => WusulError::NotFound(error_message),
-                StatusCode::TOO_MANY_REQUESTS => WusulError::RateLimitExceeded,
-                StatusCode::REQUEST_TIMEOUT => WusulError::Timeout,
-                StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
-                    WusulError::AuthError(error_message)
+        let secret = self.secret.shared_secret().await?;
+        let shared_secret = secret.expose_secret();
+
+        let (signature, url, body) = match (self.signature_version, &method) {
+            (SignatureVersion::V1, &Method::GET) => {
+                let (_, signature, sig_payload) =
+                    create_get_auth_headers(&self.account_id, shared_secret, payload);
+                let url = with_query(&url, payload, Some(sig_payload))?;
+                (Some(signature), url, None)
+            }
+            (SignatureVersion::V1, &Method::DELETE) => {
+                // The API signs its default payload for DELETE requests, which carry no body
+                let (_, signature, _) = create_auth_headers(&self.account_id, shared_secret, None);
+                (Some(signature), url, None)
+            }
+            (SignatureVersion::V1, _) => {
+                let (_, signature, body) =
+                    create_auth_headers(&self.account_id, shared_secret, payload);
+                (Some(signature), url, Some(body))
+            }
+            // v2 signatures are made per attempt, see `send`
+            (SignatureVersion::V2, &Method::GET) => (None, with_query(&url, payload, None)?, None),
+            (SignatureVersion::V2, &Method::DELETE) => (None, url, None),
+            (SignatureVersion::V2, _) => (None, url, Some(canonical_body(payload))),
+        };
+
+        // Caller headers go first so they cannot replace the authentication headers
+        let mut headers = options.headers.clone();
+        if let Some(language) = &options.accept_language {
+            headers.insert(ACCEPT_LANGUAGE, header_value(language)?);
+        } else if let Some(locale) = &self.locale {
+            headers.insert(ACCEPT_LANGUAGE, locale.clone());
+        }
+        headers.insert("X-ACCT-ID", header_value(&self.account_id)?);
+        match &signature {
+            Some(signature) => {
+                headers.insert(SIGNATURE_HEADER, header_value(signature)?);
+            }
+            None => {
+                headers.insert(SIGNATURE_VERSION_HEADER, HeaderValue::from_static("2"));
+            }
+        }
+        headers.insert("Content-Type", HeaderValue::from_static("application/json"));
+        let retry_policy = options.retry_policy.as_ref().unwrap_or(&self.retry_policy);
+        let generated_key = generated_idempotency_key(&method, options, retry_policy);
+        if let Some(key) = options.idempotency_key.as_ref().or(generated_key.as_ref()) {
+            headers.insert(IDEMPOTENCY_KEY_HEADER, header_value(key)?);
+        }
+
+        let v2_secret = signature.is_none().then_some(&secret);
+        let request = HttpRequest {
+            method,
+            url,
+            headers,
+            body,
+            timeout: options.timeout,
+        };
+
+        #[cfg(feature = "tracing")]
+        let result = {
+            let operation = Operation::start(&endpoint, self.trace_context.as_deref());
+            let span = operation.span.clone();
+            tracing::Instrument::instrument(
+                self.send(request, retry_policy, v2_secret, &operation),
+                span,
+            )
+            .await
+        };
+        #[cfg(not(feature = "tracing"))]
+        let result = self.send(request, retry_policy, v2_secret).await;
+
+        match generated_key {
+            Some(key) => result.map_err(|err| with_idempotency_key(err, key)),
+            None => result,
+        }
+    }
+
+    /// Send a request, retrying transient failures according to the retry policy
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: HttpRequest,
+        retry_policy: &RetryPolicy,
+        v2_secret: Option<&SecretString>,
+        #[cfg(feature = "tracing")] operation: &Operation,
+    ) -> Result<ApiResult<T>> {
+        let replayable = is_replayable(&request.method, &request.headers);
+        let mut attempt = 1;
+
+        loop {
+            if let Some(circuit_breaker) = &self.circuit_breaker {
+                let admitted = circuit_breaker.admit();
+                #[cfg(feature = "tracing")]
+                if let Err(err) = &admitted {
+                    operation.finish(attempt, Some(err));
+                }
+                admitted?;
+            }
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+
+            let mut attempt_request = request.clone();
+            if let Some(secret) = v2_secret {
+                sign_v2(&mut attempt_request, secret.expose_secret())?;
+            }
+            #[cfg(feature = "tracing")]
+            let traced = operation.start_attempt(attempt, &mut attempt_request.headers);
+
+            let chain = Next::new(self.transport.as_ref(), &self.middleware);
+            let outcome = chain.run(attempt_request);
+            #[cfg(feature = "tracing")]
+            let outcome = tracing::Instrument::instrument(outcome, traced.span.clone());
+            let outcome = outcome.await;
+            #[cfg(feature = "tracing")]
+            traced.finish(operation, &outcome);
+            if let Some(circuit_breaker) = &self.circuit_breaker {
+                circuit_breaker.record(&outcome);
+            }
+
+            let (result, server_delay) = match outcome {
+                Ok(response) => {
+                    if let Some(rate_limiter) = &self.rate_limiter {
+                        rate_limiter.update(&response.headers);
+                    }
+                    let server_delay = retry_after(response.status, &response.headers);
+                    (handle_response(response, server_delay), server_delay)
                 }
-                _ => WusulError::ApiError {
-                    status: status_code,
-                    message: error_message,
-                },
-            })
+                Err(err) => (Err(err), None),
+            };
+
+            let err = match result {
+                Ok(response) => {
+                    #[cfg(feature = "tracing")]
+                    operation.finish(attempt, None);
+                    return Ok(response);
+                }
+                Err(err) => err,
+            };
+
+            let delay = if replayable {
+                retry_policy.delay_for(&err, attempt, server_delay)
+            } else {
+                None
+            };
+            match delay {
+                Some(delay) => {
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => {
+                    #[cfg(feature = "tracing")]
+                    operation.finish(attempt, Some(&err));
+                    return Err(err);
+                }
+            }
         }
     }
 }
 
+/// Percent-encode `id` for use as a single path segment
+///
+/// Rejects IDs that would address another resource once in the path: empty
+/// IDs, `.` and `..`. `name` identifies the parameter in the error.
+pub(crate) fn path_segment(name: &str, id: &str) -> Result<String> {
+    if id.is_empty() || id == "." || id == ".." {
+        return Err(WusulError::InvalidParameter(format!(
+            "{} must be a non-empty ID, got {:?}",
+            name, id
+        )));
+    }
+    Ok(utf8_percent_encode(id, PATH_SEGMENT).to_string())
+}
+
+/// Append `payload` as query parameters to `url`, followed by `sig_payload`
+fn with_query(
+    url: &str,
+    payload: Option<&serde_json::Value>,
+    sig_payload: Option<String>,
+) -> Result<String> {
+    let mut query = match payload {
+        Some(params) => encode_query(params)?,
+        None => Vec::new(),
+    };
+    if let Some(sig_payload) = sig_payload {
+        query.push(("sig_payload".to_string(), sig_payload));
+    }
+    if query.is_empty() {
+        return Ok(url.to_string());
+    }
+
+    let url = url::Url::parse_with_params(url, &query)
+        .map_err(|err| WusulError::ConfigError(format!("Invalid URL {}: {}", url, err)))?;
+    Ok(url.to_string())
+}
+
+/// Sign one attempt of a v2 request with a fresh timestamp and nonce
+///
+/// Every attempt is signed again so that retries are neither replays nor
+/// outside the server's clock-skew window.
+fn sign_v2(request: &mut HttpRequest, shared_secret: &str) -> Result<()> {
+    let url = url::Url::parse(&request.url)
+        .map_err(|err| WusulError::ConfigError(format!("Invalid URL {}: {}", request.url, err)))?;
+    let path = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    let timestamp = chrono::Utc::now().timestamp();
+    let nonce = uuid::Uuid::new_v4().to_string();
+
+    let signature = create_signature_v2(
+        shared_secret,
+        request.method.as_str(),
+        &path,
+        timestamp,
+        &nonce,
+        request.body.as_deref().unwrap_or_default(),
+    );
+    request
+        .headers
+        .insert(TIMESTAMP_HEADER, header_value(&timestamp.to_string())?);
+    request.headers.insert(NONCE_HEADER, header_value(&nonce)?);
+    request
+        .headers
+        .insert(SIGNATURE_HEADER, header_value(&signature)?);
+    Ok(())
+}
+
+/// A key for a `POST` the caller did not supply one for
+///
+/// Generated when retries are enabled, so that every attempt of the call
+/// carries the same key. Other methods get none: they are safe to replay, and
+/// the API only honours the key on `POST` requests.
+fn generated_idempotency_key(
+    method: &Method,
+    options: &RequestOptions,
+    retry_policy: &RetryPolicy,
+) -> Option<String> {
+    let generate = options.idempotency_key.is_none()
+        && *method == Method::POST
+        && retry_policy.max_attempts > 1;
+    generate.then(generate_idempotency_key)
+}
+
+/// Handle HTTP response, unwrapping the `{success, data, metadata, error}` envelope
+fn handle_response<T: DeserializeOwned>(
+    response: HttpResponse,
+    retry_after: Option<Duration>,
+) -> Result<ApiResult<T>> {
+    let WithMetadata { data, metadata } = decode_envelope(response.status, &response.body)
+        .map_err(|err| match err {
+            WusulError::RateLimitExceeded { error, .. } => {
+                WusulError::RateLimitExceeded { error, retry_after }
+            }
+            other => other,
+        })?;
+
+    Ok(ApiResult {
+        data,
+        meta: ResponseMeta::new(response.status, &response.headers, metadata),
+    })
+}
+
+/// Build a header value, rejecting characters that are not allowed in headers
+fn header_value(value: &str) -> Result<HeaderValue> {
+    HeaderValue::from_str(value)
+        .map_err(|_| WusulError::InvalidParameter(format!("Invalid header value: {:?}", value)))
+}
+
+/// Flatten query parameters into key/value pairs
+///
+/// Nested values use the bracket notation understood by the API's query parser
+/// (`qs`): objects become `key[field]=value`, arrays become `key[0]=value`.
+/// Strings are sent as-is, other scalars use their JSON representation and
+/// `null` is sent as an empty value.
+fn encode_query(params: &serde_json::Value) -> Result<Vec<(String, String)>> {
+    let obj = params.as_object().ok_or_else(|| {
+        WusulError::InvalidParameter("Query parameters must be a JSON object".to_string())
+    })?;
+
+    let mut pairs = Vec::new();
+    for (key, value) in obj {
+        push_query_pairs(&mut pairs, key.clone(), value);
+    }
+    Ok(pairs)
+}
+
+fn push_query_pairs(pairs: &mut Vec<(String, String)>, key: String, value: &serde_json::Value) {
+    match value {
+        serde_json::Value::Object(obj) => {
+            for (field, nested) in obj {
+                push_query_pairs(pairs, format!("{}[{}]", key, field), nested);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (index, nested) in items.iter().enumerate() {
+                push_query_pairs(pairs, format!("{}[{}]", key, index), nested);
+            }
+        }
+        serde_json::Value::String(s) => pairs.push((key, s.clone())),
+        serde_json::Value::Null => pairs.push((key, String::new())),
+        other => pairs.push((key, other.to_string())),
+    }
+}
+
+/// Decode a response body into its data and metadata, or the matching error
+fn decode_envelope<T: DeserializeOwned>(status: StatusCode, body: &str) -> Result<WithMetadata<T>> {
+    let envelope = match serde_json::from_str::<ResponseEnvelope<serde_json::Value>>(body) {
+        Ok(envelope) => envelope,
+        Err(err) if status.is_success() => return Err(WusulError::SerializationError(err)),
+        Err(_) => {
+            let message = if body.is_empty() {
+                status
+                    .canonical_reason()
+                    .unwrap_or("Unknown error")
+                    .to_string()
+            } else {
+                body.to_string()
+            };
+            return Err(error_for_status(status, ErrorBody::unknown(message)));
+        }
+    };
+
+    if !status.is_success() || !envelope.success {
+        let error = envelope
+            .error
+            .unwrap_or_else(|| ErrorBody::unknown("Unknown error".to_string()));
+        return Err(error_for_status(status, error));
+    }
+
+    let mut metadata = envelope.metadata.unwrap_or_default();
+    let data = envelope.data.unwrap_or(serde_json::Value::Null);
+
+    // List endpoints return pagination inside `data`; surface it on the metadata as well
+    if metadata.pagination.is_none() {
+        if let Some(pagination) = data.get("pagination") {
+            metadata.pagination = serde_json::from_value(pagination.clone()).ok();
+        }
+    }
+
+    Ok(WithMetadata {
+        data: serde_json::from_value(data)?,
+        metadata,
+    })
+}
+
+/// Map an HTTP status and server error body to the matching error variant
+fn error_for_status(status: StatusCode, error: ErrorBody) -> WusulError {
+    match status {
+        StatusCode::NOT_FOUND => WusulError::NotFound(error),
+        StatusCode::TOO_MANY_REQUESTS => WusulError::RateLimitExceeded {
+            error,
+            retry_after: None,
+        },
+        StatusCode::REQUEST_TIMEOUT => WusulError::Timeout,
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => WusulError::AuthError(error),
+        _ => WusulError::ApiError {
+            status: status.as_u16(),
+            error,
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ErrorCode;
 
     #[test]
     fn test_http_client_creation() {
@@ -202,4 +687,183 @@ mod tests {
         assert_eq!(client.base_url, "https://api.wusul.io");
         assert_eq!(client.account_id, "test_account");
     }
+
+    #[test]
+    fn test_decode_envelope_data_and_metadata() {
+        let body = r#"{
+            "success": true,
+            "data": {"id": "0xabc", "state": "PENDING"},
+            "metadata": {"timestamp": "2024-01-01T00:00:00.000Z", "requestId": "req_1"}
+        }"#;
+
+        let response: WithMetadata<serde_json::Value> =
+            decode_envelope(StatusCode::CREATED, body).unwrap();
+
+        assert_eq!(response.data["id"], "0xabc");
+        assert_eq!(response.metadata.request_id.as_deref(), Some("req_1"));
+        assert!(response.metadata.timestamp.is_some());
+    }
+
+    #[test]
+    fn test_decode_envelope_lifts_pagination() {
+        let body = r#"{
+            "success": true,
+            "data": {"items": [], "pagination": {"page": 2, "limit": 10, "total": 15, "totalPages": 2}},
+            "metadata": {"timestamp": "2024-01-01T00:00:00.000Z"}
+        }"#;
+
+        let response: WithMetadata<serde_json::Value> =
+            decode_envelope(StatusCode::OK, body).unwrap();
+        let pagination = response.metadata.pagination.unwrap();
+
+        assert_eq!(pagination.page, 2);
+        assert_eq!(pagination.total_pages, 2);
+    }
+
+    #[test]
+    fn test_decode_envelope_without_metadata() {
+        let body = r#"{"success": true, "data": {"status": "healthy"}}"#;
+
+        let response: WithMetadata<serde_json::Value> =
+            decode_envelope(StatusCode::OK, body).unwrap();
+
+        assert_eq!(response.data["status"], "healthy");
+        assert!(response.metadata.timestamp.is_none());
+    }
+
+    #[test]
+    fn test_decode_envelope_validation_error() {
+        let body = r#"{
+            "success": false,
+            "error": {
+                "code": "VALIDATION_ERROR",
+                "message": "Request validation failed",
+                "details": [{"field": "full_name", "message": "Full name is required"}]
+            },
+            "metadata": {"timestamp": "2024-01-01T00:00:00.000Z"}
+        }"#;
+
+        let err = decode_envelope::<serde_json::Value>(StatusCode::BAD_REQUEST, body).unwrap_err();
+
+        match &err {
+            WusulError::ApiError { status, error } => {
+                assert_eq!(*status, 400);
+                assert_eq!(error.code, "VALIDATION_ERROR");
+            }
+            other => panic!("Expected ApiError, got {:?}", other),
+        }
+        assert_eq!(err.code(), Some("VALIDATION_ERROR"));
+        assert_eq!(err.details().unwrap()[0]["field"], "full_name");
+    }
+
+    #[test]
+    fn test_decode_envelope_status_mapping() {
+        let body = r#"{"success": false, "error": {"code": "ACCESS_PASS_NOT_FOUND", "message": "Access pass not found"}}"#;
+        let err = decode_envelope::<serde_json::Value>(StatusCode::NOT_FOUND, body).unwrap_err();
+        assert!(matches!(err, WusulError::NotFound(ref e) if e.code == "ACCESS_PASS_NOT_FOUND"));
+
+        let body = r#"{"success": false, "error": {"code": "UNAUTHORIZED", "message": "Invalid signature"}}"#;
+        let err = decode_envelope::<serde_json::Value>(StatusCode::UNAUTHORIZED, body).unwrap_err();
+        assert!(matches!(err, WusulError::AuthError(ref e) if e.message == "Invalid signature"));
+
+        let body = r#"{"success": false, "error": {"code": "RATE_LIMIT_EXCEEDED", "message": "Too many requests"}}"#;
+        let err =
+            decode_envelope::<serde_json::Value>(StatusCode::TOO_MANY_REQUESTS, body).unwrap_err();
+        assert!(matches!(err, WusulError::RateLimitExceeded { .. }));
+    }
+
+    #[test]
+    fn test_decode_envelope_error_without_message() {
+        let body = r#"{"success": false, "error": {"code": "INTERNAL_ERROR"}}"#;
+        let err = decode_envelope::<serde_json::Value>(StatusCode::INTERNAL_SERVER_ERROR, body)
+            .unwrap_err();
+
+        assert_eq!(err.error_code(), Some(&ErrorCode::InternalError));
+        assert_eq!(err.server_message(), Some(""));
+    }
+
+    #[test]
+    fn test_decode_envelope_non_json_error() {
+        let err = decode_envelope::<serde_json::Value>(
+            StatusCode::BAD_GATEWAY,
+            "<html>Bad gateway</html>",
+        )
+        .unwrap_err();
+
+        match err {
+            WusulError::ApiError { status, error } => {
+                assert_eq!(status, 502);
+                assert_eq!(error.code, "UNKNOWN_ERROR");
+                assert_eq!(error.message, "<html>Bad gateway</html>");
+            }
+            other => panic!("Expected ApiError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decode_envelope_invalid_success_body() {
+        let err = decode_envelope::<serde_json::Value>(StatusCode::OK, "not json").unwrap_err();
+        assert!(matches!(err, WusulError::SerializationError(_)));
+    }
+
+    #[test]
+    fn test_encode_query_scalars() {
+        let params = serde_json::json!({
+            "template_id": "template_123",
+            "limit": 10,
+            "ratio": 1.5,
+            "active": true,
+            "cursor": null
+        });
+
+        let pairs = encode_query(&params).unwrap();
+
+        assert!(pairs.contains(&("template_id".to_string(), "template_123".to_string())));
+        assert!(pairs.contains(&("limit".to_string(), "10".to_string())));
+        assert!(pairs.contains(&("ratio".to_string(), "1.5".to_string())));
+        assert!(pairs.contains(&("active".to_string(), "true".to_string())));
+        assert!(pairs.contains(&("cursor".to_string(), String::new())));
+    }
+
+    #[test]
+    fn test_encode_query_arrays_and_nested() {
+        let params = serde_json::json!({
+            "states": ["ACTIVE", "SUSPENDED"],
+            "filter": {"device": "watch", "ids": [1, 2]}
+        });
+
+        let pairs = encode_query(&params).unwrap();
+
+        assert_eq!(
+            pairs,
+            vec![
+                ("filter[device]".to_string(), "watch".to_string()),
+                ("filter[ids][0]".to_string(), "1".to_string()),
+                ("filter[ids][1]".to_string(), "2".to_string()),
+                ("states[0]".to_string(), "ACTIVE".to_string()),
+                ("states[1]".to_string(), "SUSPENDED".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_query_rejects_non_object() {
+        let err = encode_query(&serde_json::json!(["a", "b"])).unwrap_err();
+        assert!(matches!(err, WusulError::InvalidParameter(_)));
+    }
+
+    #[test]
+    fn test_path_segment() {
+        assert_eq!(path_segment("id", "pass_123").unwrap(), "pass_123");
+        assert_eq!(path_segment("id", "0xp4-5.1~d").unwrap(), "0xp4-5.1~d");
+        assert_eq!(
+            path_segment("id", "a/b?c=1#d e").unwrap(),
+            "a%2Fb%3Fc%3D1%23d%20e"
+        );
+
+        for id in ["", ".", ".."] {
+            let err = path_segment("id", id).unwrap_err();
+            assert!(matches!(err, WusulError::InvalidParameter(_)));
+        }
+    }
 }
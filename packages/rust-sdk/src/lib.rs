@@ -9,6 +9,8 @@
 //! - Full async/await support with tokio
 //! - Type-safe API with comprehensive error handling
 //! - Automatic request authentication
+//! - Optional blocking client for synchronous programs (`blocking` feature)
+//! - Optional `tracing` spans and W3C trace context propagation (`tracing` feature)
 //!
 //! ## Installation
 //!
@@ -23,7 +25,7 @@
 //! ## Quick Start
 //!
 //! ```no_run
-//! use wusul::{Wusul, types::IssueAccessPassParams};
+//! use wusul::{Wusul, types::{IssueAccessPassParams, ListAccessPassesParams}};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -37,17 +39,20 @@
 //!     let params = IssueAccessPassParams {
 //!         card_template_id: "template_123".to_string(),
 //!         full_name: "John Doe".to_string(),
-//!         start_date: "2024-01-01".to_string(),
-//!         expiration_date: "2024-12-31".to_string(),
+//!         start_date: "2024-01-01T00:00:00Z".to_string(),
+//!         expiration_date: "2024-12-31T23:59:59Z".to_string(),
 //!         email: Some("john@example.com".to_string()),
+//!         site_code: Some("100".to_string()),
+//!         card_number: Some("42069".to_string()),
 //!         ..Default::default()
 //!     };
 //!
 //!     let access_pass = client.access_passes.issue(params).await?;
 //!     println!("Created access pass: {}", access_pass.id);
 //!
-//!     // List all access passes
-//!     let passes = client.access_passes.list(None).await?;
+//!     // List the access passes issued from a template
+//!     let params = ListAccessPassesParams::new("template_123");
+//!     let passes = client.access_passes.list(params).await?;
 //!     println!("Total passes: {}", passes.len());
 //!
 //!     Ok(())
@@ -74,18 +79,44 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! Or load it from `WUSUL_*` environment variables or a credentials file
+//! profile (see [`config`]):
+//!
+//! ```no_run
+//! use wusul::{Wusul, WusulConfig};
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = Wusul::with_config(WusulConfig::from_env()?)?;
+//! let staging = Wusul::with_config(WusulConfig::from_profile("staging")?)?;
+//! # Ok(())
+//! # }
+//! ```
 
 pub mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod canonical_json;
+pub mod circuit_breaker;
+pub mod config;
 pub mod error;
 pub mod http_client;
+pub mod middleware;
+pub mod pagination;
+pub mod rate_limit;
 pub mod resources;
+pub mod retry;
+pub mod secret;
+#[cfg(feature = "tracing")]
+pub mod telemetry;
+pub mod transport;
 pub mod types;
 
 use error::{Result, WusulError};
 use http_client::HttpClient;
 use resources::{AccessPasses, Console};
 use std::sync::Arc;
-pub use types::WusulConfig;
+pub use types::{RequestOptions, WusulConfig};
 
 /// Main client for interacting with the Wusul API
 ///
@@ -112,6 +143,10 @@ pub use types::WusulConfig;
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Cloning the client is cheap: clones share the connection pool and the
+/// configuration, including any rate limiter.
+#[derive(Clone)]
 pub struct Wusul {
     http: Arc<HttpClient>,
     /// Resource for managing access passes
@@ -190,19 +225,14 @@ impl Wusul {
             ));
         }
 
-        if config.shared_secret.is_empty() {
+        if config.shared_secret.is_empty() && config.secret_provider.is_none() {
             return Err(WusulError::ConfigError(
                 "Shared secret is required".to_string(),
             ));
         }
 
         // Create HTTP client
-        let http = Arc::new(HttpClient::new(
-            config.account_id,
-            config.shared_secret,
-            config.base_url,
-            config.timeout,
-        )?);
+        let http = Arc::new(HttpClient::from_config(config)?);
 
         // Initialize resources
         let access_passes = AccessPasses::new(Arc::clone(&http));
@@ -289,7 +319,7 @@ mod tests {
             .with_timeout(std::time::Duration::from_secs(45));
 
         assert_eq!(config.account_id, "account");
-        assert_eq!(config.shared_secret, "secret");
+        assert_eq!(config.shared_secret.expose_secret(), "secret");
         assert_eq!(config.base_url, "https://custom.api");
         assert_eq!(config.timeout, std::time::Duration::from_secs(45));
     }
@@ -0,0 +1,221 @@
+//! Request/response middleware.
+//!
+//! Middleware registered on [`WusulConfig`] wraps every attempt of every
+//! request, in registration order: the first middleware sees the request first
+//! and the response last. Each middleware receives the signed request and a
+//! [`Next`] handle that passes it on to the rest of the chain and finally to
+//! the [`Transport`]. That makes it the place to add headers (tenant or
+//! correlation IDs), log requests and responses, or measure latency.
+//!
+//! The signature covers the method, URL query and body, so middleware may add
+//! or change headers but must not alter those.
+//!
+//! [`WusulConfig`]: crate::WusulConfig
+
+use crate::error::Result;
+use crate::transport::{
+    parse_header, HeaderName, HeaderValue, HttpRequest, HttpResponse, Transport,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// A step in the request/response chain
+///
+/// # Example
+///
+/// ```no_run
+/// use async_trait::async_trait;
+/// use std::time::Instant;
+/// use wusul::error::Result;
+/// use wusul::middleware::{Middleware, Next};
+/// use wusul::transport::{HttpRequest, HttpResponse};
+/// use wusul::WusulConfig;
+///
+/// struct Latency;
+///
+/// #[async_trait]
+/// impl Middleware for Latency {
+///     async fn handle(&self, request: HttpRequest, next: Next<'_>) -> Result<HttpResponse> {
+///         let started = Instant::now();
+///         let url = request.url.clone();
+///         let result = next.run(request).await;
+///         println!("{} took {:?}", url, started.elapsed());
+///         result
+///     }
+/// }
+///
+/// let config = WusulConfig::new("account_id".to_string(), "shared_secret".to_string())
+///     .with_middleware(Latency);
+/// ```
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Handle a request, usually by passing it to `next` and inspecting the result
+    async fn handle(&self, request: HttpRequest, next: Next<'_>) -> Result<HttpResponse>;
+}
+
+/// The remainder of the middleware chain
+pub struct Next<'a> {
+    transport: &'a dyn Transport,
+    middleware: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(transport: &'a dyn Transport, middleware: &'a [Arc<dyn Middleware>]) -> Self {
+        Self {
+            transport,
+            middleware,
+        }
+    }
+
+    /// Pass the request to the next middleware, or send it if this is the last one
+    pub async fn run(self, request: HttpRequest) -> Result<HttpResponse> {
+        match self.middleware.split_first() {
+            Some((current, rest)) => {
+                current
+                    .handle(request, Next::new(self.transport, rest))
+                    .await
+            }
+            None => self.transport.send(request).await,
+        }
+    }
+}
+
+/// Middleware that sets a header on every request
+///
+/// # Example
+///
+/// ```no_run
+/// use wusul::middleware::SetHeader;
+/// use wusul::WusulConfig;
+///
+/// # fn example() -> wusul::error::Result<()> {
+/// let config = WusulConfig::new("account_id".to_string(), "shared_secret".to_string())
+///     .with_middleware(SetHeader::new("X-Tenant-ID", "tenant_42")?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SetHeader {
+    name: HeaderName,
+    value: HeaderValue,
+}
+
+impl SetHeader {
+    /// Create the middleware, checking that `name` and `value` are valid header text
+    pub fn new(name: &str, value: &str) -> Result<Self> {
+        let (name, value) = parse_header(name, value)?;
+        Ok(Self { name, value })
+    }
+}
+
+#[async_trait]
+impl Middleware for SetHeader {
+    async fn handle(&self, mut request: HttpRequest, next: Next<'_>) -> Result<HttpResponse> {
+        request
+            .headers
+            .insert(self.name.clone(), self.value.clone());
+        next.run(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::{HeaderMap, Method, StatusCode};
+    use std::sync::Mutex;
+
+    /// Transport that echoes the request headers back as the response headers
+    struct Echo;
+
+    #[async_trait]
+    impl Transport for Echo {
+        async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+            Ok(HttpResponse {
+                status: StatusCode::OK,
+                headers: request.headers,
+                body: String::new(),
+            })
+        }
+    }
+
+    struct Record {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Middleware for Record {
+        async fn handle(&self, request: HttpRequest, next: Next<'_>) -> Result<HttpResponse> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} request", self.name));
+            let response = next.run(request).await;
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} response", self.name));
+            response
+        }
+    }
+
+    fn request() -> HttpRequest {
+        HttpRequest {
+            method: Method::GET,
+            url: "https://api.test/health".to_string(),
+            headers: HeaderMap::new(),
+            body: None,
+            timeout: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empty_chain_reaches_transport() {
+        let response = Next::new(&Echo, &[]).run(request()).await.unwrap();
+        assert_eq!(response.status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_chain_runs_in_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let chain: Vec<Arc<dyn Middleware>> = vec![
+            Arc::new(Record {
+                name: "first",
+                log: Arc::clone(&log),
+            }),
+            Arc::new(Record {
+                name: "second",
+                log: Arc::clone(&log),
+            }),
+        ];
+
+        Next::new(&Echo, &chain).run(request()).await.unwrap();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "first request",
+                "second request",
+                "second response",
+                "first response"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_header() {
+        let chain: Vec<Arc<dyn Middleware>> = vec![Arc::new(
+            SetHeader::new("X-Tenant-ID", "tenant_42").unwrap(),
+        )];
+
+        let response = Next::new(&Echo, &chain).run(request()).await.unwrap();
+
+        assert_eq!(response.headers["x-tenant-id"], "tenant_42");
+    }
+
+    #[test]
+    fn test_set_header_rejects_invalid_text() {
+        assert!(SetHeader::new("X Tenant", "tenant_42").is_err());
+        assert!(SetHeader::new("X-Tenant-ID", "tenant\n42").is_err());
+    }
+}
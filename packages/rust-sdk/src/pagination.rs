@@ -0,0 +1,179 @@
+//! Paginated list endpoints.
+//!
+//! List endpoints return one page at a time together with its
+//! [`Pagination`]. A [`Page`] carries both, so callers can tell whether more
+//! results exist. The `*_all` resource methods walk every page instead and
+//! return a [`Stream`](futures::Stream) of the items: pages are requested
+//! only as the stream is consumed, up to `prefetch` pages ahead of the
+//! caller, and the stream ends after yielding the first error.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures::TryStreamExt;
+//! use wusul::{Wusul, types::ListAccessPassesParams};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
+//!
+//! let params = ListAccessPassesParams::new("template_123");
+//! let mut passes = client.access_passes.list_all(params);
+//! while let Some(pass) = passes.try_next().await? {
+//!     println!("{}", pass.id);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::types::Pagination;
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+/// Number of pages the `*_all` methods request ahead of the caller by default
+pub const DEFAULT_PREFETCH: usize = 2;
+
+/// One page of a list endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// The items on this page
+    pub items: Vec<T>,
+    /// Position of this page in the full result set, when the endpoint reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
+}
+
+impl<T> Page<T> {
+    /// Whether pages after this one exist
+    pub fn has_next(&self) -> bool {
+        self.next_page().is_some()
+    }
+
+    /// Number of the following page, if there is one
+    pub fn next_page(&self) -> Option<u32> {
+        self.pagination
+            .filter(|pagination| pagination.page < pagination.total_pages)
+            .map(|pagination| pagination.page + 1)
+    }
+
+    /// Total number of items across all pages, when the endpoint reports it
+    pub fn total(&self) -> Option<u64> {
+        self.pagination.map(|pagination| pagination.total)
+    }
+
+    /// The items on this page
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T> IntoIterator for Page<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+/// Stream the items of every page, starting at `first_page`
+///
+/// `fetch` requests a single page by number. The first page is requested when
+/// the stream is first polled; its [`Pagination`] decides how many pages
+/// follow. Up to `prefetch` of those are requested concurrently, and items
+/// are yielded in page order. After an error is yielded the stream ends and
+/// pending requests are dropped.
+///
+/// The `*_all` resource methods are built on this; use it directly to page
+/// through an endpoint with custom request handling.
+pub fn stream<T, F, Fut>(
+    first_page: u32,
+    prefetch: usize,
+    fetch: F,
+) -> BoxStream<'static, Result<T>>
+where
+    T: Send + 'static,
+    F: Fn(u32) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<Page<T>>> + Send + 'static,
+{
+    let prefetch = prefetch.max(1);
+    let rest_fetch = fetch.clone();
+
+    let items = stream::once(fetch(first_page)).flat_map(move |first| {
+        let (items, next_pages) = match first {
+            Ok(page) => {
+                let next_pages = page
+                    .next_page()
+                    .zip(page.pagination)
+                    .map(|(next, pagination)| next..=pagination.total_pages);
+                (page.items.into_iter().map(Ok).collect(), next_pages)
+            }
+            Err(err) => (vec![Err(err)], None),
+        };
+
+        let fetch = rest_fetch.clone();
+        let rest = stream::iter(next_pages.into_iter().flatten())
+            .map(fetch)
+            .buffered(prefetch)
+            .flat_map(|page| {
+                stream::iter(match page {
+                    Ok(page) => page.items.into_iter().map(Ok).collect(),
+                    Err(err) => vec![Err(err)],
+                })
+            });
+        stream::iter(items).chain(rest)
+    });
+
+    // Checks for a failure before polling again, so no page is requested after an error
+    stream::unfold((items.boxed(), false), |(mut items, failed)| async move {
+        if failed {
+            return None;
+        }
+        let item = items.next().await?;
+        let failed = item.is_err();
+        Some((item, (items, failed)))
+    })
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+
+    fn page(page: u32, total_pages: u32) -> Page<u32> {
+        Page {
+            items: vec![page],
+            pagination: Some(Pagination {
+                page,
+                limit: 1,
+                total: u64::from(total_pages),
+                total_pages,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_next_page() {
+        assert_eq!(page(1, 3).next_page(), Some(2));
+        assert_eq!(page(3, 3).next_page(), None);
+        assert_eq!(page(1, 0).next_page(), None);
+
+        let unpaginated = Page {
+            items: vec![1],
+            pagination: None,
+        };
+        assert!(!unpaginated.has_next());
+    }
+
+    #[tokio::test]
+    async fn test_stream_starts_at_first_page() {
+        let items: Vec<u32> = stream(2, 1, |n| async move { Ok(page(n, 4)) })
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(items, [2, 3, 4]);
+    }
+}
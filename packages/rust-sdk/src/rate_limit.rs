@@ -0,0 +1,305 @@
+//! Client-side rate limiting.
+//!
+//! The API limits each client to a number of requests per window (100 per 15
+//! minutes for most routes) and answers with `429 Too Many Requests` beyond
+//! that. A [`RateLimiter`] installed with [`WusulConfig::with_rate_limiter`]
+//! keeps the client under its budget instead: it is a token bucket that every
+//! attempt takes a token from, and requests wait for a token when the bucket is
+//! empty.
+//!
+//! The bucket follows the `RateLimit-Limit`, `RateLimit-Remaining` and
+//! `RateLimit-Reset` headers the API returns, so it stays in step with the
+//! server's count even when other processes share the same quota. When the
+//! server reports no requests remaining, the limiter holds every request until
+//! the window resets.
+//!
+//! A limiter is cheap to clone and clones share their budget, so one limiter
+//! can be installed on several clients, and a client and its clones share it
+//! across tasks.
+//!
+//! [`WusulConfig::with_rate_limiter`]: crate::WusulConfig::with_rate_limiter
+
+use crate::transport::HeaderMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Window of the API's rate limiters
+const API_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+/// Token bucket shared by every request of one or more clients
+///
+/// # Example
+///
+/// ```no_run
+/// use wusul::rate_limit::RateLimiter;
+/// use wusul::{Wusul, WusulConfig};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let limiter = RateLimiter::api();
+///
+/// // Both clients draw from the same budget
+/// let first = Wusul::with_config(
+///     WusulConfig::new("account_id".to_string(), "shared_secret".to_string())
+///         .with_rate_limiter(limiter.clone()),
+/// )?;
+/// let second = Wusul::with_config(
+///     WusulConfig::new("account_id".to_string(), "shared_secret".to_string())
+///         .with_rate_limiter(limiter),
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+struct Bucket {
+    capacity: f64,
+    window: Duration,
+    tokens: f64,
+    refilled_at: Instant,
+    /// Set when the server reports the quota exhausted
+    blocked_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        let rate = self.capacity / self.window.as_secs_f64();
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * rate).min(self.capacity);
+        self.refilled_at = now;
+    }
+
+    /// Take a token, or return how long to wait for one
+    fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(until) = self.blocked_until {
+            if now < until {
+                return Err(until - now);
+            }
+            // The server's window has reset
+            self.blocked_until = None;
+            self.tokens = self.capacity;
+            self.refilled_at = now;
+        }
+
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+
+        let rate = self.capacity / self.window.as_secs_f64();
+        Err(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+    }
+}
+
+impl RateLimiter {
+    /// Allow `requests` requests per `window`, starting with a full bucket
+    pub fn new(requests: u32, window: Duration) -> Self {
+        let capacity = f64::from(requests.max(1));
+        Self {
+            bucket: Arc::new(Mutex::new(Bucket {
+                capacity,
+                window: window.max(Duration::from_millis(1)),
+                tokens: capacity,
+                refilled_at: Instant::now(),
+                blocked_until: None,
+            })),
+        }
+    }
+
+    /// Budget of the API's general rate limiter: 100 requests per 15 minutes
+    pub fn api() -> Self {
+        Self::new(100, API_WINDOW)
+    }
+
+    /// Budget of the API's strict rate limiter: 20 requests per 15 minutes
+    pub fn strict() -> Self {
+        Self::new(20, API_WINDOW)
+    }
+
+    /// Wait until a request may be sent, and count it against the budget
+    pub async fn acquire(&self) {
+        loop {
+            let wait = self.bucket.lock().unwrap().try_take(Instant::now());
+            match wait {
+                Ok(()) => return,
+                Err(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    /// Number of requests that can be sent right now without waiting
+    pub fn available(&self) -> u32 {
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        if bucket.blocked_until.is_some_and(|until| now < until) {
+            return 0;
+        }
+        bucket.refill(now);
+        bucket.tokens.floor() as u32
+    }
+
+    /// Adjust the budget from the `RateLimit-*` headers of a response
+    pub fn update(&self, headers: &HeaderMap) {
+        let RateLimitInfo {
+            limit,
+            remaining,
+            reset,
+        } = match RateLimitInfo::from_headers(headers) {
+            Some(info) => info,
+            None => return,
+        };
+
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        bucket.refill(now);
+
+        if let Some(limit) = limit.filter(|limit| *limit > 0) {
+            bucket.capacity = limit as f64;
+            bucket.tokens = bucket.tokens.min(bucket.capacity);
+        }
+        match (remaining, reset) {
+            (Some(0), Some(reset)) => {
+                bucket.tokens = 0.0;
+                bucket.blocked_until = Some(now + reset);
+            }
+            (Some(remaining), _) => {
+                bucket.tokens = bucket.tokens.min(remaining as f64);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bucket = self.bucket.lock().unwrap();
+        f.debug_struct("RateLimiter")
+            .field("capacity", &bucket.capacity)
+            .field("window", &bucket.window)
+            .field("tokens", &bucket.tokens)
+            .finish()
+    }
+}
+
+/// The `RateLimit-*` headers of a response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Requests allowed per window, from `RateLimit-Limit`
+    pub limit: Option<u64>,
+    /// Requests left in the current window, from `RateLimit-Remaining`
+    pub remaining: Option<u64>,
+    /// Time until the window resets, from `RateLimit-Reset`
+    pub reset: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    /// Parse the `RateLimit-*` headers, or `None` if the response has none
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let info = Self {
+            limit: header_u64(headers, "RateLimit-Limit"),
+            remaining: header_u64(headers, "RateLimit-Remaining"),
+            reset: header_u64(headers, "RateLimit-Reset").map(Duration::from_secs),
+        };
+        (info.limit.is_some() || info.remaining.is_some() || info.reset.is_some()).then_some(info)
+    }
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_requests_within_budget_do_not_wait() {
+        let limiter = RateLimiter::new(3, Duration::from_secs(60));
+        let started = Instant::now();
+
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+
+        assert_eq!(started.elapsed(), Duration::ZERO);
+        assert_eq!(limiter.available(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_empty_bucket_waits_for_refill() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let started = Instant::now();
+
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+
+        // One token comes back every 30 seconds
+        assert!(started.elapsed() >= Duration::from_secs(30));
+        assert!(started.elapsed() < Duration::from_secs(31));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_clones_share_the_budget() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let clone = limiter.clone();
+
+        limiter.acquire().await;
+        clone.acquire().await;
+
+        assert_eq!(limiter.available(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_remaining_header_lowers_budget() {
+        let limiter = RateLimiter::api();
+        limiter.update(&headers(&[
+            ("RateLimit-Limit", "100"),
+            ("RateLimit-Remaining", "5"),
+            ("RateLimit-Reset", "600"),
+        ]));
+
+        assert_eq!(limiter.available(), 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_limit_header_sets_capacity() {
+        let limiter = RateLimiter::api();
+        limiter.update(&headers(&[("RateLimit-Limit", "20")]));
+
+        assert_eq!(limiter.available(), 20);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_exhausted_quota_waits_for_reset() {
+        let limiter = RateLimiter::api();
+        limiter.update(&headers(&[
+            ("RateLimit-Remaining", "0"),
+            ("RateLimit-Reset", "120"),
+        ]));
+        assert_eq!(limiter.available(), 0);
+
+        let started = Instant::now();
+        limiter.acquire().await;
+
+        assert_eq!(started.elapsed(), Duration::from_secs(120));
+        assert_eq!(limiter.available(), 99);
+    }
+}
@@ -1,11 +1,52 @@
 use crate::error::Result;
-use crate::http_client::HttpClient;
+use crate::http_client::{path_segment, Endpoint, HttpClient};
+use crate::pagination::{self, Page, DEFAULT_PREFETCH};
+use crate::transport::Method;
 use crate::types::{
-    AccessPass, ApiResponse, IssueAccessPassParams, ListAccessPassesParams, UpdateAccessPassParams,
+    AccessPass, ApiResult, IssueAccessPassParams, ListAccessPassesParams, RequestOptions,
+    UpdateAccessPassParams,
 };
+use futures::stream::{self, BoxStream, StreamExt};
 use std::sync::Arc;
 
+/// Number of access passes [`AccessPasses::get_many`] fetches concurrently by default
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+const ISSUE: Endpoint = Endpoint::new("access_passes.issue", Method::POST, "/v1/access-passes");
+const GET: Endpoint = Endpoint::new("access_passes.get", Method::GET, "/v1/access-passes/{id}");
+const LIST: Endpoint = Endpoint::new("access_passes.list", Method::GET, "/v1/access-passes");
+const UPDATE: Endpoint = Endpoint::new(
+    "access_passes.update",
+    Method::PATCH,
+    "/v1/access-passes/{id}",
+);
+const SUSPEND: Endpoint = Endpoint::new(
+    "access_passes.suspend",
+    Method::POST,
+    "/v1/access-passes/{id}/suspend",
+);
+const RESUME: Endpoint = Endpoint::new(
+    "access_passes.resume",
+    Method::POST,
+    "/v1/access-passes/{id}/resume",
+);
+const UNLINK: Endpoint = Endpoint::new(
+    "access_passes.unlink",
+    Method::POST,
+    "/v1/access-passes/{id}/unlink",
+);
+const DELETE: Endpoint = Endpoint::new(
+    "access_passes.delete",
+    Method::POST,
+    "/v1/access-passes/{id}/delete",
+);
+
 /// Resource for managing access passes
+///
+/// Each method has a `*_with_options` variant that takes [`RequestOptions`].
+/// Those sending a single request return an [`ApiResult`], with details of
+/// the response, such as its request ID and rate limit, alongside the data.
+#[derive(Clone)]
 pub struct AccessPasses {
     http: Arc<HttpClient>,
 }
@@ -33,9 +74,11 @@ impl AccessPasses {
     /// let params = IssueAccessPassParams {
     ///     card_template_id: "template_123".to_string(),
     ///     full_name: "John Doe".to_string(),
-    ///     start_date: "2024-01-01".to_string(),
-    ///     expiration_date: "2024-12-31".to_string(),
+    ///     start_date: "2024-01-01T00:00:00Z".to_string(),
+    ///     expiration_date: "2024-12-31T23:59:59Z".to_string(),
     ///     employee_id: Some("EMP001".to_string()),
+    ///     site_code: Some("100".to_string()),
+    ///     card_number: Some("42069".to_string()),
     ///     email: Some("john@example.com".to_string()),
     ///     phone_number: Some("+1234567890".to_string()),
     ///     ..Default::default()
@@ -47,15 +90,178 @@ impl AccessPasses {
     /// # }
     /// ```
     pub async fn issue(&self, params: IssueAccessPassParams) -> Result<AccessPass> {
+        self.issue_with_options(params, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Issue a new access pass with per-call request options
+    ///
+    /// Passing the same idempotency key again returns the originally issued
+    /// access pass instead of issuing a duplicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameters for creating the access pass
+    /// * `options` - Options for this request, e.g. an idempotency key
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wusul::{Wusul, RequestOptions, types::IssueAccessPassParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let params = IssueAccessPassParams {
+    ///     card_template_id: "template_123".to_string(),
+    ///     full_name: "John Doe".to_string(),
+    ///     start_date: "2024-01-01T00:00:00Z".to_string(),
+    ///     expiration_date: "2024-12-31T23:59:59Z".to_string(),
+    ///     employee_id: Some("EMP001".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let options = RequestOptions::new().with_idempotency_key("issue-EMP001".to_string());
+    ///
+    /// let response = client.access_passes.issue_with_options(params, &options).await?;
+    /// println!("Created access pass: {}", response.data.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn issue_with_options(
+        &self,
+        params: IssueAccessPassParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
         let payload = serde_json::to_value(&params)?;
-        self.http.post("/v1/access-passes", Some(&payload)).await
+        self.http
+            .request(ISSUE, "/v1/access-passes", Some(&payload), options)
+            .await
+    }
+
+    /// Retrieve a single access pass
+    ///
+    /// Returns [`WusulError::NotFound`](crate::error::WusulError::NotFound) if
+    /// no access pass with this ID exists in the account, and
+    /// [`WusulError::InvalidParameter`](crate::error::WusulError::InvalidParameter)
+    /// without sending a request if the ID is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_pass_id` - The ID of the access pass to retrieve
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wusul::Wusul;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let access_pass = client.access_passes.get("pass_123").await?;
+    /// println!("{:?} is {:?}", access_pass.full_name, access_pass.state);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get(&self, access_pass_id: &str) -> Result<AccessPass> {
+        self.get_with_options(access_pass_id, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Retrieve a single access pass with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `access_pass_id` - The ID of the access pass to retrieve
+    /// * `options` - Options for this request
+    pub async fn get_with_options(
+        &self,
+        access_pass_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        let id = path_segment("access_pass_id", access_pass_id)?;
+        self.http
+            .request(GET, &format!("/v1/access-passes/{}", id), None, options)
+            .await
+    }
+
+    /// Retrieve several access passes by ID
+    ///
+    /// Up to [`DEFAULT_CONCURRENCY`] passes are requested at once. Each ID is
+    /// returned with its own result, in the order given, so one unknown ID
+    /// does not fail the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_pass_ids` - The IDs of the access passes to retrieve
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wusul::Wusul;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// for (id, result) in client.access_passes.get_many(["pass_123", "pass_456"]).await {
+    ///     match result {
+    ///         Ok(access_pass) => println!("{}: {:?}", id, access_pass.state),
+    ///         Err(err) => println!("{}: {}", id, err),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_many<I>(&self, access_pass_ids: I) -> Vec<(String, Result<AccessPass>)>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.get_many_with_options(
+            access_pass_ids,
+            &RequestOptions::default(),
+            DEFAULT_CONCURRENCY,
+        )
+        .await
+    }
+
+    /// Retrieve several access passes by ID with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `access_pass_ids` - The IDs of the access passes to retrieve
+    /// * `options` - Options applied to every request
+    /// * `concurrency` - Maximum number of requests in flight, at least 1
+    pub async fn get_many_with_options<I>(
+        &self,
+        access_pass_ids: I,
+        options: &RequestOptions,
+        concurrency: usize,
+    ) -> Vec<(String, Result<AccessPass>)>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        stream::iter(access_pass_ids)
+            .map(|id| async move {
+                let id = id.into();
+                let result = self
+                    .get_with_options(&id, options)
+                    .await
+                    .map(ApiResult::into_data);
+                (id, result)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
     }
 
     /// List access passes with optional filtering
     ///
     /// # Arguments
     ///
-    /// * `params` - Optional parameters for filtering the list
+    /// * `params` - The card template ID and optional filters for the list
     ///
     /// # Example
     ///
@@ -65,27 +271,158 @@ impl AccessPasses {
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let params = ListAccessPassesParams {
-    ///     state: Some(AccessPassState::Active),
-    ///     limit: Some(10),
-    ///     ..Default::default()
-    /// };
+    /// let params = ListAccessPassesParams::new("template_123")
+    ///     .with_state(AccessPassState::Active)
+    ///     .with_limit(10);
     ///
-    /// let passes = client.access_passes.list(Some(params)).await?;
+    /// let passes = client.access_passes.list(params).await?;
     /// println!("Found {} active passes", passes.len());
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn list(&self, params: Option<ListAccessPassesParams>) -> Result<Vec<AccessPass>> {
-        let query = params
-            .map(|p| serde_json::to_value(p).ok())
-            .flatten();
+    pub async fn list(&self, params: ListAccessPassesParams) -> Result<Vec<AccessPass>> {
+        self.list_with_options(params, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// List access passes with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The card template ID and optional filters for the list
+    /// * `options` - Options for this request
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wusul::{Wusul, RequestOptions, types::ListAccessPassesParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let params = ListAccessPassesParams::new("template_123");
+    /// let response = client
+    ///     .access_passes
+    ///     .list_with_options(params, &RequestOptions::default())
+    ///     .await?;
+    /// println!("{} passes, request {:?}", response.data.len(), response.meta.request_id);
+    /// if let Some(rate_limit) = response.meta.rate_limit {
+    ///     println!("{:?} requests left", rate_limit.remaining);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_with_options(
+        &self,
+        params: ListAccessPassesParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<Vec<AccessPass>>> {
+        self.list_page_with_options(params, options)
+            .await
+            .map(|response| response.map(Page::into_items))
+    }
+
+    /// Fetch one page of access passes
+    ///
+    /// Unlike [`list`](Self::list), the page carries its pagination, so callers
+    /// can tell whether more passes exist. Select the page with
+    /// `params.page`.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The card template ID and optional filters for the list
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wusul::{Wusul, types::ListAccessPassesParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let params = ListAccessPassesParams::new("template_123")
+    ///     .with_page(2)
+    ///     .with_limit(25);
+    ///
+    /// let page = client.access_passes.list_page(params).await?;
+    /// println!("{} of {:?} passes, more: {}", page.items.len(), page.total(), page.has_next());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_page(&self, params: ListAccessPassesParams) -> Result<Page<AccessPass>> {
+        self.list_page_with_options(params, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Fetch one page of access passes with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The card template ID and optional filters for the list
+    /// * `options` - Options for this request
+    pub async fn list_page_with_options(
+        &self,
+        params: ListAccessPassesParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<Page<AccessPass>>> {
+        let query = serde_json::to_value(&params)?;
 
         self.http
-            .get("/v1/access-passes", query.as_ref())
+            .request(LIST, "/v1/access-passes", Some(&query), options)
             .await
     }
 
+    /// Stream the access passes of every page
+    ///
+    /// Starts at `params.page`, or the first page, and requests the following
+    /// pages as the stream is consumed. The stream ends after the first error.
+    /// See [`pagination`](crate::pagination).
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The card template ID and optional filters for the list; `limit` sets the page size
+    pub fn list_all(
+        &self,
+        params: ListAccessPassesParams,
+    ) -> BoxStream<'static, Result<AccessPass>> {
+        self.list_all_with_options(params, &RequestOptions::default(), DEFAULT_PREFETCH)
+    }
+
+    /// Stream the access passes of every page with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The card template ID and optional filters for the list; `limit` sets the page size
+    /// * `options` - Options applied to every page request
+    /// * `prefetch` - Number of pages requested ahead of the consumer, at least 1
+    pub fn list_all_with_options(
+        &self,
+        params: ListAccessPassesParams,
+        options: &RequestOptions,
+        prefetch: usize,
+    ) -> BoxStream<'static, Result<AccessPass>> {
+        let first_page = params.page.unwrap_or(1);
+        let resource = self.clone();
+        let options = options.clone();
+
+        pagination::stream(first_page, prefetch, move |page| {
+            let resource = resource.clone();
+            let options = options.clone();
+            let params = ListAccessPassesParams {
+                page: Some(page),
+                ..params.clone()
+            };
+            async move {
+                resource
+                    .list_page_with_options(params, &options)
+                    .await
+                    .map(ApiResult::into_data)
+            }
+        })
+    }
+
     /// Update an existing access pass
     ///
     /// # Arguments
@@ -103,7 +440,7 @@ impl AccessPasses {
     /// let params = UpdateAccessPassParams {
     ///     access_pass_id: "pass_123".to_string(),
     ///     full_name: Some("Jane Doe".to_string()),
-    ///     email: Some("jane@example.com".to_string()),
+    ///     title: Some("Engineering Manager".to_string()),
     ///     ..Default::default()
     /// };
     ///
@@ -113,12 +450,30 @@ impl AccessPasses {
     /// # }
     /// ```
     pub async fn update(&self, params: UpdateAccessPassParams) -> Result<AccessPass> {
-        let access_pass_id = params.access_pass_id.clone();
+        self.update_with_options(params, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Update an existing access pass with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameters with the access pass ID and fields to update
+    /// * `options` - Options for this request
+    pub async fn update_with_options(
+        &self,
+        params: UpdateAccessPassParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        let id = path_segment("access_pass_id", &params.access_pass_id)?;
         let payload = serde_json::to_value(&params)?;
         self.http
-            .patch(
-                &format!("/v1/access-passes/{}", access_pass_id),
+            .request(
+                UPDATE,
+                &format!("/v1/access-passes/{}", id),
                 Some(&payload),
+                options,
             )
             .await
     }
@@ -137,14 +492,29 @@ impl AccessPasses {
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let response = client.access_passes.suspend("pass_123").await?;
-    /// println!("Suspend result: {:?}", response.message);
+    /// let access_pass = client.access_passes.suspend("pass_123").await?;
+    /// println!("Pass state: {:?}", access_pass.state);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn suspend(&self, access_pass_id: &str) -> Result<ApiResponse> {
-        self.http
-            .post(&format!("/v1/access-passes/{}/suspend", access_pass_id), None)
+    pub async fn suspend(&self, access_pass_id: &str) -> Result<AccessPass> {
+        self.suspend_with_options(access_pass_id, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Suspend an access pass with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `access_pass_id` - The ID of the access pass to suspend
+    /// * `options` - Options for this request
+    pub async fn suspend_with_options(
+        &self,
+        access_pass_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        self.transition(SUSPEND, access_pass_id, "suspend", options)
             .await
     }
 
@@ -162,14 +532,29 @@ impl AccessPasses {
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let response = client.access_passes.resume("pass_123").await?;
-    /// println!("Resume result: {:?}", response.message);
+    /// let access_pass = client.access_passes.resume("pass_123").await?;
+    /// println!("Pass state: {:?}", access_pass.state);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn resume(&self, access_pass_id: &str) -> Result<ApiResponse> {
-        self.http
-            .post(&format!("/v1/access-passes/{}/resume", access_pass_id), None)
+    pub async fn resume(&self, access_pass_id: &str) -> Result<AccessPass> {
+        self.resume_with_options(access_pass_id, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Resume a suspended access pass with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `access_pass_id` - The ID of the access pass to resume
+    /// * `options` - Options for this request
+    pub async fn resume_with_options(
+        &self,
+        access_pass_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        self.transition(RESUME, access_pass_id, "resume", options)
             .await
     }
 
@@ -187,14 +572,29 @@ impl AccessPasses {
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let response = client.access_passes.unlink("pass_123").await?;
-    /// println!("Unlink result: {:?}", response.message);
+    /// let access_pass = client.access_passes.unlink("pass_123").await?;
+    /// println!("Pass state: {:?}", access_pass.state);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn unlink(&self, access_pass_id: &str) -> Result<ApiResponse> {
-        self.http
-            .post(&format!("/v1/access-passes/{}/unlink", access_pass_id), None)
+    pub async fn unlink(&self, access_pass_id: &str) -> Result<AccessPass> {
+        self.unlink_with_options(access_pass_id, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Unlink an access pass from the device with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `access_pass_id` - The ID of the access pass to unlink
+    /// * `options` - Options for this request
+    pub async fn unlink_with_options(
+        &self,
+        access_pass_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        self.transition(UNLINK, access_pass_id, "unlink", options)
             .await
     }
 
@@ -212,49 +612,49 @@ impl AccessPasses {
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let response = client.access_passes.delete("pass_123").await?;
-    /// println!("Delete result: {:?}", response.message);
+    /// let access_pass = client.access_passes.delete("pass_123").await?;
+    /// println!("Pass state: {:?}", access_pass.state);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn delete(&self, access_pass_id: &str) -> Result<ApiResponse> {
-        self.http
-            .delete(&format!("/v1/access-passes/{}", access_pass_id))
+    pub async fn delete(&self, access_pass_id: &str) -> Result<AccessPass> {
+        self.delete_with_options(access_pass_id, &RequestOptions::default())
             .await
+            .map(ApiResult::into_data)
     }
-}
 
-impl Default for IssueAccessPassParams {
-    fn default() -> Self {
-        Self {
-            card_template_id: String::new(),
-            full_name: String::new(),
-            start_date: String::new(),
-            expiration_date: String::new(),
-            employee_id: None,
-            tag_id: None,
-            site_code: None,
-            card_number: None,
-            email: None,
-            phone_number: None,
-            classification: None,
-            metadata: None,
-        }
+    /// Permanently delete an access pass with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `access_pass_id` - The ID of the access pass to delete
+    /// * `options` - Options for this request
+    pub async fn delete_with_options(
+        &self,
+        access_pass_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        self.transition(DELETE, access_pass_id, "delete", options)
+            .await
     }
-}
 
-impl Default for UpdateAccessPassParams {
-    fn default() -> Self {
-        Self {
-            access_pass_id: String::new(),
-            full_name: None,
-            email: None,
-            phone_number: None,
-            classification: None,
-            start_date: None,
-            expiration_date: None,
-            metadata: None,
-        }
+    /// Move an access pass to another state through its action endpoint
+    async fn transition(
+        &self,
+        endpoint: Endpoint<'static>,
+        access_pass_id: &str,
+        action: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<AccessPass>> {
+        let id = path_segment("access_pass_id", access_pass_id)?;
+        self.http
+            .request(
+                endpoint,
+                &format!("/v1/access-passes/{}/{}", id, action),
+                None,
+                options,
+            )
+            .await
     }
 }
 
@@ -270,9 +670,11 @@ mod tests {
     }
 
     #[test]
-    fn test_list_params_default() {
-        let params = ListAccessPassesParams::default();
-        assert!(params.card_template_id.is_none());
+    fn test_list_params_new() {
+        let params = ListAccessPassesParams::new("template_123").with_page(2);
+        assert_eq!(params.card_template_id, "template_123");
+        assert_eq!(params.page, Some(2));
+        assert!(params.state.is_none());
         assert!(params.limit.is_none());
     }
 
@@ -280,6 +682,6 @@ mod tests {
     fn test_update_params_default() {
         let params = UpdateAccessPassParams::default();
         assert!(params.access_pass_id.is_empty());
-        assert!(params.email.is_none());
+        assert!(params.full_name.is_none());
     }
 }
@@ -1,14 +1,48 @@
 use crate::error::Result;
-use crate::http_client::HttpClient;
+use crate::http_client::{Endpoint, HttpClient};
+use crate::pagination::{self, Page, DEFAULT_PREFETCH};
+use crate::transport::Method;
 use crate::types::{
-    ApiResponse, CardTemplate, CreateCardTemplateParams, EventLogEntry, ReadEventLogParams,
-    UpdateCardTemplateParams,
+    ApiResult, CardTemplate, CreateCardTemplateParams, EventLogEntry, ReadEventLogParams,
+    RequestOptions, UpdateCardTemplateParams,
 };
+use futures::stream::BoxStream;
 use std::sync::Arc;
 
+const CREATE_TEMPLATE: Endpoint = Endpoint::new(
+    "console.create_template",
+    Method::POST,
+    "/v1/console/card-templates",
+);
+const READ_TEMPLATE: Endpoint = Endpoint::new(
+    "console.read_template",
+    Method::GET,
+    "/v1/console/card-templates/{id}",
+);
+const UPDATE_TEMPLATE: Endpoint = Endpoint::new(
+    "console.update_template",
+    Method::PATCH,
+    "/v1/console/card-templates/{id}",
+);
+const PUBLISH_TEMPLATE: Endpoint = Endpoint::new(
+    "console.publish_template",
+    Method::POST,
+    "/v1/console/card-templates/{id}/publish",
+);
+const EVENT_LOG: Endpoint = Endpoint::new(
+    "console.event_log",
+    Method::GET,
+    "/v1/console/card-templates/{id}/logs",
+);
+
 /// Resource for enterprise console operations (template management)
 ///
 /// Note: Console operations are only available for ENTERPRISE tier accounts
+///
+/// Each method has a `*_with_options` variant that takes [`RequestOptions`].
+/// Those sending a single request return an [`ApiResult`], with details of
+/// the response alongside the data.
+#[derive(Clone)]
 pub struct Console {
     http: Arc<HttpClient>,
 }
@@ -35,8 +69,7 @@ impl Console {
     ///
     /// let design = CardTemplateDesign {
     ///     background_color: Some("#000000".to_string()),
-    ///     foreground_color: Some("#FFFFFF".to_string()),
-    ///     logo_url: Some("https://example.com/logo.png".to_string()),
+    ///     label_color: Some("#FFFFFF".to_string()),
     ///     ..Default::default()
     /// };
     ///
@@ -45,6 +78,9 @@ impl Console {
     ///     platform: Platform::Apple,
     ///     use_case: UseCase::EmployeeBadge,
     ///     protocol: Protocol::Seos,
+    ///     allow_on_multiple_devices: None,
+    ///     watch_count: None,
+    ///     iphone_count: None,
     ///     design: Some(design),
     ///     support_info: None,
     ///     metadata: None,
@@ -56,9 +92,30 @@ impl Console {
     /// # }
     /// ```
     pub async fn create_template(&self, params: CreateCardTemplateParams) -> Result<CardTemplate> {
+        self.create_template_with_options(params, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Create a new card template with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameters for creating the card template
+    /// * `options` - Options for this request, e.g. an idempotency key
+    pub async fn create_template_with_options(
+        &self,
+        params: CreateCardTemplateParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<CardTemplate>> {
         let payload = serde_json::to_value(&params)?;
         self.http
-            .post("/v1/console/card-templates", Some(&payload))
+            .request(
+                CREATE_TEMPLATE,
+                "/v1/console/card-templates",
+                Some(&payload),
+                options,
+            )
             .await
     }
 
@@ -77,13 +134,34 @@ impl Console {
     /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
     /// let template = client.console.read_template("template_123").await?;
-    /// println!("Template name: {}", template.name);
+    /// println!("Template name: {:?}", template.name);
     /// # Ok(())
     /// # }
     /// ```
     pub async fn read_template(&self, card_template_id: &str) -> Result<CardTemplate> {
+        self.read_template_with_options(card_template_id, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Read a card template by ID with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `card_template_id` - The ID of the card template to retrieve
+    /// * `options` - Options for this request
+    pub async fn read_template_with_options(
+        &self,
+        card_template_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<CardTemplate>> {
         self.http
-            .get(&format!("/v1/console/card-templates/{}", card_template_id), None)
+            .request(
+                READ_TEMPLATE,
+                &format!("/v1/console/card-templates/{}", card_template_id),
+                None,
+                options,
+            )
             .await
     }
 
@@ -110,8 +188,7 @@ impl Console {
     ///     card_template_id: "template_123".to_string(),
     ///     name: Some("Updated Employee Badge".to_string()),
     ///     design: Some(design),
-    ///     support_info: None,
-    ///     metadata: None,
+    ///     ..Default::default()
     /// };
     ///
     /// let updated = client.console.update_template(params).await?;
@@ -120,12 +197,29 @@ impl Console {
     /// # }
     /// ```
     pub async fn update_template(&self, params: UpdateCardTemplateParams) -> Result<CardTemplate> {
-        let card_template_id = params.card_template_id.clone();
+        self.update_template_with_options(params, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Update an existing card template with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameters with the template ID and fields to update
+    /// * `options` - Options for this request
+    pub async fn update_template_with_options(
+        &self,
+        params: UpdateCardTemplateParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<CardTemplate>> {
         let payload = serde_json::to_value(&params)?;
         self.http
-            .patch(
-                &format!("/v1/console/card-templates/{}", card_template_id),
+            .request(
+                UPDATE_TEMPLATE,
+                &format!("/v1/console/card-templates/{}", params.card_template_id),
                 Some(&payload),
+                options,
             )
             .await
     }
@@ -144,88 +238,224 @@ impl Console {
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
-    /// let response = client.console.publish_template("template_123").await?;
-    /// println!("Publish result: {:?}", response.message);
+    /// let template = client.console.publish_template("template_123").await?;
+    /// println!("Publish status: {:?}", template.publish_status);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn publish_template(&self, card_template_id: &str) -> Result<ApiResponse> {
+    pub async fn publish_template(&self, card_template_id: &str) -> Result<CardTemplate> {
+        self.publish_template_with_options(card_template_id, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Publish a card template with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `card_template_id` - The ID of the card template to publish
+    /// * `options` - Options for this request
+    pub async fn publish_template_with_options(
+        &self,
+        card_template_id: &str,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<CardTemplate>> {
         self.http
-            .post(
+            .request(
+                PUBLISH_TEMPLATE,
                 &format!("/v1/console/card-templates/{}/publish", card_template_id),
                 None,
+                options,
             )
             .await
     }
 
-    /// Read event logs with optional filtering
+    /// Read the event log of a card template with optional filtering
     ///
     /// # Arguments
     ///
-    /// * `params` - Optional parameters for filtering the event log
+    /// * `params` - The card template ID and optional filters for the event log
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use wusul::{Wusul, types::ReadEventLogParams};
+    /// use wusul::{Wusul, types::{EventTypeFilter, ReadEventLogParams}};
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
     ///
     /// let params = ReadEventLogParams {
-    ///     access_pass_id: Some("pass_123".to_string()),
-    ///     event_type: Some("access_granted".to_string()),
+    ///     card_template_id: "template_123".to_string(),
+    ///     event_type: Some(EventTypeFilter::Install),
     ///     limit: Some(50),
     ///     ..Default::default()
     /// };
     ///
-    /// let logs = client.console.event_log(Some(params)).await?;
+    /// let logs = client.console.event_log(params).await?;
     /// println!("Found {} events", logs.len());
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn event_log(
+    pub async fn event_log(&self, params: ReadEventLogParams) -> Result<Vec<EventLogEntry>> {
+        self.event_log_with_options(params, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Read the event log of a card template with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The card template ID and optional filters for the event log
+    /// * `options` - Options for this request
+    pub async fn event_log_with_options(
         &self,
-        params: Option<ReadEventLogParams>,
-    ) -> Result<Vec<EventLogEntry>> {
-        let query = params
-            .map(|p| serde_json::to_value(p).ok())
-            .flatten();
+        params: ReadEventLogParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<Vec<EventLogEntry>>> {
+        self.event_log_page_with_options(params, options)
+            .await
+            .map(|response| response.map(Page::into_items))
+    }
+
+    /// Fetch one page of the event log of a card template
+    ///
+    /// Unlike [`event_log`](Self::event_log), the page carries its pagination,
+    /// so callers can tell whether more events exist. Select the page with
+    /// `params.page`.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The card template ID and optional filters for the event log
+    pub async fn event_log_page(&self, params: ReadEventLogParams) -> Result<Page<EventLogEntry>> {
+        self.event_log_page_with_options(params, &RequestOptions::default())
+            .await
+            .map(ApiResult::into_data)
+    }
+
+    /// Fetch one page of the event log of a card template with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The card template ID and optional filters for the event log
+    /// * `options` - Options for this request
+    pub async fn event_log_page_with_options(
+        &self,
+        params: ReadEventLogParams,
+        options: &RequestOptions,
+    ) -> Result<ApiResult<Page<EventLogEntry>>> {
+        let card_template_id = params.card_template_id.clone();
+        let query = serde_json::to_value(&params)?;
 
         self.http
-            .get("/v1/console/event-log", query.as_ref())
+            .request(
+                EVENT_LOG,
+                &format!("/v1/console/card-templates/{}/logs", card_template_id),
+                Some(&query),
+                options,
+            )
             .await
     }
+
+    /// Stream the events of every page of a card template's event log
+    ///
+    /// Starts at `params.page`, or the first page, and requests the following
+    /// pages as the stream is consumed. The stream ends after the first error.
+    /// See [`pagination`](crate::pagination).
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The card template ID and optional filters; `limit` sets the page size
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::TryStreamExt;
+    /// use wusul::{Wusul, types::ReadEventLogParams};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Wusul::new("account_id".to_string(), "shared_secret".to_string())?;
+    ///
+    /// let params = ReadEventLogParams {
+    ///     card_template_id: "template_123".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// let events: Vec<_> = client.console.event_log_all(params).try_collect().await?;
+    /// println!("Found {} events", events.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn event_log_all(
+        &self,
+        params: ReadEventLogParams,
+    ) -> BoxStream<'static, Result<EventLogEntry>> {
+        self.event_log_all_with_options(params, &RequestOptions::default(), DEFAULT_PREFETCH)
+    }
+
+    /// Stream the events of every page of a card template's event log with per-call request options
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The card template ID and optional filters; `limit` sets the page size
+    /// * `options` - Options applied to every page request
+    /// * `prefetch` - Number of pages requested ahead of the consumer, at least 1
+    pub fn event_log_all_with_options(
+        &self,
+        params: ReadEventLogParams,
+        options: &RequestOptions,
+        prefetch: usize,
+    ) -> BoxStream<'static, Result<EventLogEntry>> {
+        let first_page = params.page.unwrap_or(1);
+        let resource = self.clone();
+        let options = options.clone();
+
+        pagination::stream(first_page, prefetch, move |page| {
+            let resource = resource.clone();
+            let options = options.clone();
+            let params = ReadEventLogParams {
+                page: Some(page),
+                ..params.clone()
+            };
+            async move {
+                resource
+                    .event_log_page_with_options(params, &options)
+                    .await
+                    .map(ApiResult::into_data)
+            }
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{Platform, Protocol, UseCase, CardTemplateDesign, SupportInfo};
+    use crate::types::{CardTemplateDesign, Platform, Protocol, SupportInfo, UseCase};
 
     #[test]
     fn test_card_template_design_default() {
         let design = CardTemplateDesign {
             background_color: None,
-            foreground_color: None,
             label_color: None,
-            logo_url: None,
-            hero_image_url: None,
-            strip_image_url: None,
+            label_secondary_color: None,
+            background_image: None,
+            logo_image: None,
+            icon_image: None,
         };
         assert!(design.background_color.is_none());
-        assert!(design.logo_url.is_none());
+        assert!(design.logo_image.is_none());
     }
 
     #[test]
     fn test_support_info_creation() {
         let info = SupportInfo {
-            email: None,
-            phone: None,
-            website: None,
+            support_url: None,
+            support_phone_number: None,
+            support_email: None,
+            privacy_policy_url: None,
+            terms_and_conditions_url: None,
         };
-        assert!(info.email.is_none());
-        assert!(info.phone.is_none());
+        assert!(info.support_email.is_none());
+        assert!(info.support_phone_number.is_none());
     }
 
     #[test]
@@ -235,6 +465,9 @@ mod tests {
             platform: Platform::Apple,
             use_case: UseCase::EmployeeBadge,
             protocol: Protocol::Seos,
+            allow_on_multiple_devices: None,
+            watch_count: None,
+            iphone_count: None,
             design: None,
             support_info: None,
             metadata: None,
@@ -242,5 +475,6 @@ mod tests {
 
         let json = serde_json::to_value(&params).unwrap();
         assert!(json.is_object());
+        assert_eq!(json["use_case"], "EMPLOYEE_BADGE");
     }
 }
@@ -0,0 +1,415 @@
+//! Automatic retries for transient failures.
+//!
+//! Requests that fail with a retryable status (429 and 5xx by default), a
+//! timeout or a connection error are retried with exponential backoff and
+//! jitter. When the server says how long to wait, via `Retry-After` or the
+//! `RateLimit-Reset` header sent by the API's rate limiter, that delay is used
+//! instead.
+//!
+//! Only requests that are safe to replay are retried: `GET`, `PATCH` and
+//! `DELETE` requests always are (the API's `PATCH` endpoints set fields to the
+//! given values), while `POST` requests are only retried when they carry an
+//! `Idempotency-Key` header. While retries are enabled, `POST` requests
+//! without a caller-supplied key get a generated one, so every attempt of the
+//! same call carries the same key. The API stores the response to a keyed
+//! `POST` for 24 hours and returns it for repeats of the key instead of
+//! performing the operation again. A repeat that arrives while the first
+//! attempt is still being processed fails with `IDEMPOTENCY_KEY_IN_USE`; it is
+//! retried with backoff like a 5xx response.
+//!
+//! When a `POST` with a generated key fails after it may have reached the API,
+//! the error is wrapped in [`WusulError::OutcomeUnknown`] with the key, so the
+//! caller can retry with it later.
+
+use crate::error::{ErrorCode, WusulError};
+use crate::transport::{HeaderMap, Method, StatusCode};
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use std::time::Duration;
+
+/// Header used to make non-idempotent requests safe to retry
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Policy controlling how failed requests are retried
+///
+/// # Example
+///
+/// ```no_run
+/// use wusul::{retry::RetryPolicy, WusulConfig};
+/// use std::time::Duration;
+///
+/// let config = WusulConfig::new("account_id".to_string(), "shared_secret".to_string())
+///     .with_retry_policy(
+///         RetryPolicy::default()
+///             .with_max_attempts(5)
+///             .with_base_delay(Duration::from_millis(250)),
+///     );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for every further retry
+    pub base_delay: Duration,
+    /// Upper bound for a single delay. A server asking for a longer wait is not retried.
+    pub max_delay: Duration,
+    /// HTTP statuses that are retried
+    pub retry_statuses: Vec<u16>,
+    /// Whether requests that timed out are retried
+    pub retry_on_timeout: bool,
+    /// Whether requests that failed to connect are retried
+    pub retry_on_connect_error: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            retry_statuses: vec![429, 500, 502, 503, 504],
+            retry_on_timeout: true,
+            retry_on_connect_error: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_retry_statuses(mut self, retry_statuses: Vec<u16>) -> Self {
+        self.retry_statuses = retry_statuses;
+        self
+    }
+
+    pub fn with_retry_on_timeout(mut self, retry_on_timeout: bool) -> Self {
+        self.retry_on_timeout = retry_on_timeout;
+        self
+    }
+
+    pub fn with_retry_on_connect_error(mut self, retry_on_connect_error: bool) -> Self {
+        self.retry_on_connect_error = retry_on_connect_error;
+        self
+    }
+
+    /// Whether the error is one this policy retries
+    ///
+    /// `IDEMPOTENCY_KEY_IN_USE` conflicts are always retryable: the first
+    /// request with the key is still running, and a later attempt gets its result.
+    pub fn is_retryable(&self, error: &WusulError) -> bool {
+        match error {
+            WusulError::ApiError { error, .. } if error.code == ErrorCode::IdempotencyKeyInUse => {
+                true
+            }
+            WusulError::ApiError { status, .. } => self.retry_statuses.contains(status),
+            WusulError::RateLimitExceeded { .. } => self.retry_statuses.contains(&429),
+            WusulError::Timeout => self.retry_on_timeout,
+            WusulError::ConnectError(_) => self.retry_on_connect_error,
+            WusulError::OutcomeUnknown { source, .. } => self.is_retryable(source),
+            _ => false,
+        }
+    }
+
+    /// How long to wait before retrying after `attempt` failed attempts
+    ///
+    /// Returns `None` when the request should not be retried: the attempts are
+    /// used up, the error is not retryable, or the server asked for a longer
+    /// wait than `max_delay`.
+    pub(crate) fn delay_for(
+        &self,
+        error: &WusulError,
+        attempt: u32,
+        server_delay: Option<Duration>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.is_retryable(error) {
+            return None;
+        }
+
+        match server_delay {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+
+    /// Exponential backoff with "equal jitter": a random delay between half and
+    /// all of `base_delay * 2^(attempt - 1)`, capped at `max_delay`
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+
+        let half = delay / 2;
+        half + rand::thread_rng().gen_range(Duration::ZERO..=delay - half)
+    }
+}
+
+/// Generate a random idempotency key
+pub fn generate_idempotency_key() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Whether a request may be replayed without side effects
+pub(crate) fn is_replayable(method: &Method, headers: &HeaderMap) -> bool {
+    match *method {
+        Method::POST => headers.contains_key(IDEMPOTENCY_KEY_HEADER),
+        _ => true,
+    }
+}
+
+/// Attach a generated idempotency key to the error of a `POST` that may have
+/// reached the API
+///
+/// Errors raised before the request reached the API, or after it rejected the
+/// request outright, are returned unchanged: retrying those with a new key
+/// cannot perform the operation twice.
+pub(crate) fn with_idempotency_key(error: WusulError, idempotency_key: String) -> WusulError {
+    let may_have_been_processed = match &error {
+        WusulError::ApiError { status, error } => {
+            *status >= 500 || error.code == ErrorCode::IdempotencyKeyInUse
+        }
+        WusulError::HttpError(_)
+        | WusulError::BodyError(_)
+        | WusulError::ResponseTooLarge { .. }
+        | WusulError::SerializationError(_)
+        | WusulError::Timeout => true,
+        _ => false,
+    };
+    if !may_have_been_processed {
+        return error;
+    }
+    WusulError::OutcomeUnknown {
+        idempotency_key,
+        source: Box::new(error),
+    }
+}
+
+/// How long the server asked clients to wait before the next request
+///
+/// `Retry-After` may be a number of seconds or an HTTP date. `RateLimit-Reset`
+/// (seconds until the rate limit window resets) is used when the response is a
+/// 429 or `RateLimit-Remaining` is zero.
+pub(crate) fn retry_after(status: StatusCode, headers: &HeaderMap) -> Option<Duration> {
+    if let Some(value) = header_str(headers, "Retry-After") {
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+        if let Ok(date) = DateTime::parse_from_rfc2822(value) {
+            let wait = date.with_timezone(&Utc) - Utc::now();
+            return Some(wait.to_std().unwrap_or(Duration::ZERO));
+        }
+    }
+
+    let exhausted = status == StatusCode::TOO_MANY_REQUESTS
+        || header_str(headers, "RateLimit-Remaining") == Some("0");
+    if exhausted {
+        if let Some(seconds) =
+            header_str(headers, "RateLimit-Reset").and_then(|v| v.parse::<u64>().ok())
+        {
+            return Some(Duration::from_secs(seconds));
+        }
+    }
+
+    None
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorBody;
+    use crate::transport::HeaderValue;
+
+    fn api_error(status: u16) -> WusulError {
+        WusulError::ApiError {
+            status,
+            error: ErrorBody::unknown("boom".to_string()),
+        }
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_default_policy() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_attempts, 3);
+        assert!(policy.is_retryable(&api_error(503)));
+        assert!(policy.is_retryable(&WusulError::Timeout));
+        assert!(!policy.is_retryable(&api_error(400)));
+        assert!(
+            !policy.is_retryable(&WusulError::NotFound(ErrorBody::unknown(
+                "missing".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_none_policy_never_retries() {
+        let policy = RetryPolicy::none();
+        assert_eq!(policy.delay_for(&api_error(503), 1, None), None);
+    }
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let policy = RetryPolicy::default()
+            .with_max_attempts(10)
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(700));
+
+        for _ in 0..50 {
+            let first = policy.delay_for(&api_error(503), 1, None).unwrap();
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+
+            let third = policy.delay_for(&api_error(503), 3, None).unwrap();
+            assert!(third >= Duration::from_millis(200) && third <= Duration::from_millis(400));
+
+            let capped = policy.delay_for(&api_error(503), 9, None).unwrap();
+            assert!(capped >= Duration::from_millis(350) && capped <= Duration::from_millis(700));
+        }
+    }
+
+    #[test]
+    fn test_server_delay() {
+        let policy = RetryPolicy::default().with_max_delay(Duration::from_secs(10));
+        let error = api_error(503);
+
+        assert_eq!(
+            policy.delay_for(&error, 1, Some(Duration::from_secs(2))),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            policy.delay_for(&error, 1, Some(Duration::from_secs(60))),
+            None
+        );
+        assert_eq!(policy.delay_for(&error, 3, Some(Duration::ZERO)), None);
+    }
+
+    #[test]
+    fn test_retry_after_header() {
+        let seconds = headers(&[("Retry-After", "120")]);
+        assert_eq!(
+            retry_after(StatusCode::SERVICE_UNAVAILABLE, &seconds),
+            Some(Duration::from_secs(120))
+        );
+
+        let past = headers(&[("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT")]);
+        assert_eq!(
+            retry_after(StatusCode::SERVICE_UNAVAILABLE, &past),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_reset_header() {
+        let exhausted = headers(&[("RateLimit-Remaining", "0"), ("RateLimit-Reset", "42")]);
+        assert_eq!(
+            retry_after(StatusCode::OK, &exhausted),
+            Some(Duration::from_secs(42))
+        );
+
+        let limited = headers(&[("RateLimit-Remaining", "3"), ("RateLimit-Reset", "42")]);
+        assert_eq!(retry_after(StatusCode::OK, &limited), None);
+        assert_eq!(
+            retry_after(StatusCode::TOO_MANY_REQUESTS, &limited),
+            Some(Duration::from_secs(42))
+        );
+    }
+
+    #[test]
+    fn test_post_requires_idempotency_key() {
+        assert!(is_replayable(&Method::GET, &HeaderMap::new()));
+        assert!(is_replayable(&Method::PATCH, &HeaderMap::new()));
+        assert!(!is_replayable(&Method::POST, &HeaderMap::new()));
+        assert!(is_replayable(
+            &Method::POST,
+            &headers(&[(IDEMPOTENCY_KEY_HEADER, "key_1")])
+        ));
+    }
+
+    #[test]
+    fn test_idempotency_key_in_use_is_retried() {
+        let in_use = WusulError::ApiError {
+            status: 409,
+            error: ErrorBody {
+                code: ErrorCode::IdempotencyKeyInUse,
+                message: "still processing".to_string(),
+                details: None,
+            },
+        };
+
+        assert!(RetryPolicy::default().is_retryable(&in_use));
+        assert!(!RetryPolicy::default().is_retryable(&api_error(409)));
+        assert!(RetryPolicy::default()
+            .with_retry_statuses(vec![])
+            .is_retryable(&in_use));
+    }
+
+    #[test]
+    fn test_key_is_attached_when_outcome_is_unknown() {
+        let err = with_idempotency_key(WusulError::Timeout, "key_1".to_string());
+        assert_eq!(err.idempotency_key(), Some("key_1"));
+        assert!(err.is_retryable());
+        assert!(matches!(
+            err,
+            WusulError::OutcomeUnknown { ref source, .. } if matches!(**source, WusulError::Timeout)
+        ));
+
+        let err = with_idempotency_key(api_error(502), "key_1".to_string());
+        assert_eq!(err.idempotency_key(), Some("key_1"));
+        assert_eq!(err.code(), Some("UNKNOWN_ERROR"));
+
+        for err in [
+            api_error(400),
+            WusulError::CircuitOpen {
+                retry_after: Duration::from_secs(1),
+            },
+        ] {
+            assert_eq!(
+                with_idempotency_key(err, "key_1".to_string()).idempotency_key(),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_generated_idempotency_keys_are_unique() {
+        let key = generate_idempotency_key();
+        assert_eq!(key.len(), 36);
+        assert_ne!(key, generate_idempotency_key());
+    }
+}
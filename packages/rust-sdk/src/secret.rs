@@ -0,0 +1,333 @@
+//! Handling of the shared secret.
+//!
+//! The shared secret is held in a [`SecretString`], which never prints its
+//! value through `Debug` or `Display` and wipes its memory when dropped.
+//!
+//! By default the client signs every request with the secret given to
+//! [`WusulConfig::new`]. A [`SecretProvider`] installed with
+//! [`WusulConfig::with_secret_provider`] supplies it instead and is asked for
+//! the secret on every request, so a rotated secret is picked up without
+//! rebuilding the client. The SDK ships providers that read an environment
+//! variable ([`EnvSecret`]) or a file ([`FileSecret`], cached between reads),
+//! and an in-memory [`RotatingSecret`] that a secrets-manager integration can
+//! push new values into. Implement the trait to fetch the secret from a vault
+//! directly.
+//!
+//! [`WusulConfig::new`]: crate::WusulConfig::new
+//! [`WusulConfig::with_secret_provider`]: crate::WusulConfig::with_secret_provider
+
+use crate::error::{Result, WusulError};
+use async_trait::async_trait;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::time::Instant;
+use zeroize::Zeroizing;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// How long [`FileSecret`] reuses the secret it read before reading the file again
+pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A string that is redacted when printed and zeroized when dropped
+#[derive(Clone, Default)]
+pub struct SecretString(Zeroizing<String>);
+
+impl SecretString {
+    /// Wrap `secret`
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(Zeroizing::new(secret.into()))
+    }
+
+    /// The secret value
+    ///
+    /// Avoid copying it into longer-lived buffers; copies are not zeroized.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the secret is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self::new(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        Self::new(secret)
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SecretString").field(&REDACTED).finish()
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// A source of the shared secret
+///
+/// Called once per request, before signing, so implementations that fetch the
+/// secret remotely should cache it.
+///
+/// # Example
+///
+/// ```no_run
+/// use async_trait::async_trait;
+/// use wusul::error::Result;
+/// use wusul::secret::{SecretProvider, SecretString};
+/// use wusul::WusulConfig;
+///
+/// struct Vault;
+///
+/// #[async_trait]
+/// impl SecretProvider for Vault {
+///     async fn shared_secret(&self) -> Result<SecretString> {
+///         // Fetch from the vault, or return a cached copy
+///         Ok(SecretString::new("secret_from_vault"))
+///     }
+/// }
+///
+/// let config = WusulConfig::new("account_id".to_string(), String::new())
+///     .with_secret_provider(Vault);
+/// ```
+#[async_trait]
+pub trait SecretProvider: Send + Sync {
+    /// The current shared secret
+    async fn shared_secret(&self) -> Result<SecretString>;
+}
+
+#[async_trait]
+impl SecretProvider for SecretString {
+    async fn shared_secret(&self) -> Result<SecretString> {
+        Ok(self.clone())
+    }
+}
+
+/// Reads the secret from an environment variable on every request
+#[derive(Debug, Clone)]
+pub struct EnvSecret {
+    var: String,
+}
+
+impl EnvSecret {
+    /// Read the secret from the environment variable `var`
+    pub fn new(var: impl Into<String>) -> Self {
+        Self { var: var.into() }
+    }
+}
+
+#[async_trait]
+impl SecretProvider for EnvSecret {
+    async fn shared_secret(&self) -> Result<SecretString> {
+        let secret = std::env::var(&self.var).map_err(|err| {
+            WusulError::ConfigError(format!("Failed to read {}: {}", self.var, err))
+        })?;
+        non_empty(SecretString::new(secret), &self.var)
+    }
+}
+
+/// Reads the secret from a file, re-reading it once the refresh interval has passed
+///
+/// Suits secrets mounted as files, for example by Kubernetes or a vault
+/// agent, which are replaced in place on rotation. Surrounding whitespace,
+/// such as a trailing newline, is ignored.
+///
+/// The file is read with blocking I/O on the calling task, so it is only read
+/// by the first request after [`DEFAULT_REFRESH_INTERVAL`] has passed; other
+/// requests reuse the cached secret. A rotated secret is therefore picked up
+/// within one interval. Clones share the cache.
+#[derive(Debug, Clone)]
+pub struct FileSecret {
+    path: PathBuf,
+    refresh_interval: Duration,
+    cached: Arc<RwLock<Option<(SecretString, Instant)>>>,
+}
+
+impl FileSecret {
+    /// Read the secret from the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            cached: Arc::default(),
+        }
+    }
+
+    /// Reuse the secret read from the file for `refresh_interval`
+    ///
+    /// `Duration::ZERO` reads the file on every request, blocking the executor
+    /// thread for each read.
+    pub fn with_refresh_interval(mut self, refresh_interval: Duration) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    fn read(&self) -> Result<SecretString> {
+        let contents = Zeroizing::new(std::fs::read_to_string(&self.path).map_err(|err| {
+            WusulError::ConfigError(format!(
+                "Failed to read the shared secret from {}: {}",
+                self.path.display(),
+                err
+            ))
+        })?);
+        non_empty(
+            SecretString::new(contents.trim()),
+            &self.path.display().to_string(),
+        )
+    }
+}
+
+#[async_trait]
+impl SecretProvider for FileSecret {
+    async fn shared_secret(&self) -> Result<SecretString> {
+        if let Some((secret, read_at)) = &*self.cached.read().unwrap() {
+            if read_at.elapsed() < self.refresh_interval {
+                return Ok(secret.clone());
+            }
+        }
+
+        let secret = self.read()?;
+        *self.cached.write().unwrap() = Some((secret.clone(), Instant::now()));
+        Ok(secret)
+    }
+}
+
+/// An in-memory secret that can be replaced at runtime
+///
+/// Stands in for a secrets vault: a task watching the vault calls
+/// [`rotate`](Self::rotate) and the next request is signed with the new
+/// secret. Clones share the secret.
+///
+/// # Example
+///
+/// ```no_run
+/// use wusul::secret::RotatingSecret;
+/// use wusul::{Wusul, WusulConfig};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let secret = RotatingSecret::new("initial_secret");
+/// let client = Wusul::with_config(
+///     WusulConfig::new("account_id".to_string(), String::new())
+///         .with_secret_provider(secret.clone()),
+/// )?;
+///
+/// // Later, after the secret was rotated
+/// secret.rotate("rotated_secret");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RotatingSecret {
+    secret: Arc<RwLock<SecretString>>,
+}
+
+impl RotatingSecret {
+    /// Start with `secret`
+    pub fn new(secret: impl Into<SecretString>) -> Self {
+        Self {
+            secret: Arc::new(RwLock::new(secret.into())),
+        }
+    }
+
+    /// Replace the secret; the previous one is zeroized
+    pub fn rotate(&self, secret: impl Into<SecretString>) {
+        *self.secret.write().unwrap() = secret.into();
+    }
+}
+
+#[async_trait]
+impl SecretProvider for RotatingSecret {
+    async fn shared_secret(&self) -> Result<SecretString> {
+        let secret = self.secret.read().unwrap().clone();
+        non_empty(secret, "rotating secret")
+    }
+}
+
+impl fmt::Debug for RotatingSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RotatingSecret")
+            .field("secret", &*self.secret.read().unwrap())
+            .finish()
+    }
+}
+
+fn non_empty(secret: SecretString, source: &str) -> Result<SecretString> {
+    if secret.is_empty() {
+        return Err(WusulError::ConfigError(format!(
+            "Shared secret from {} is empty",
+            source
+        )));
+    }
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_is_redacted() {
+        let secret = SecretString::new("hunter2");
+
+        assert_eq!(format!("{:?}", secret), "SecretString(\"[REDACTED]\")");
+        assert_eq!(secret.to_string(), "[REDACTED]");
+        assert_eq!(secret.expose_secret(), "hunter2");
+    }
+
+    #[tokio::test]
+    async fn test_rotating_secret() {
+        let secret = RotatingSecret::new("first");
+        let shared = secret.clone();
+
+        shared.rotate("second");
+
+        let current = secret.shared_secret().await.unwrap();
+        assert_eq!(current.expose_secret(), "second");
+        assert!(!format!("{:?}", secret).contains("second"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_file_secret_is_cached_until_refresh() {
+        let path = std::env::temp_dir().join(format!("wusul-secret-cache-{}", std::process::id()));
+        let read = |secret: &FileSecret| {
+            let secret = secret.clone();
+            async move {
+                let current = secret.shared_secret().await.unwrap();
+                current.expose_secret().to_string()
+            }
+        };
+
+        std::fs::write(&path, "first\n").unwrap();
+        let secret = FileSecret::new(&path);
+        assert_eq!(read(&secret).await, "first");
+
+        std::fs::write(&path, "second\n").unwrap();
+        assert_eq!(read(&secret).await, "first");
+        let uncached = FileSecret::new(&path).with_refresh_interval(Duration::ZERO);
+        assert_eq!(read(&uncached).await, "second");
+
+        tokio::time::advance(DEFAULT_REFRESH_INTERVAL).await;
+        assert_eq!(read(&secret).await, "second");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_empty_secret_is_rejected() {
+        let result = RotatingSecret::new("").shared_secret().await;
+
+        assert!(matches!(result, Err(WusulError::ConfigError(_))));
+    }
+}
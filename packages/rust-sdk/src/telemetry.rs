@@ -0,0 +1,232 @@
+//! `tracing` instrumentation, enabled by the `tracing` feature.
+//!
+//! Every API call runs inside a `wusul.request` span and every attempt of that
+//! call inside a child `wusul.attempt` span:
+//!
+//! | Span            | Fields                                                                                   |
+//! |-----------------|------------------------------------------------------------------------------------------|
+//! | `wusul.request` | `operation`, `http.method`, `http.route`, `http.status_code`, `attempts`, `latency_ms`, `trace_id`, `error` |
+//! | `wusul.attempt` | `attempt`, `span_id`, `http.status_code`, `latency_ms`, `error`                          |
+//!
+//! `http.route` is the path template (`/v1/access-passes/{id}/suspend`), so IDs
+//! do not end up in span fields. Request headers and bodies are never recorded:
+//! neither the shared secret nor the `X-PAYLOAD-SIG` signature can leak into
+//! traces.
+//!
+//! Each attempt also carries a W3C `traceparent` header whose parent ID is the
+//! attempt's `span_id`. All attempts of a call share one trace ID: the caller's,
+//! when a [`TraceContextSource`] is configured with
+//! [`WusulConfig::with_trace_context`] and reports one, otherwise a random ID
+//! whose trace is not marked as sampled. The `sampled` flag is passed on as
+//! reported, so the SDK never forces sampling downstream. Middleware runs after
+//! the header is set and may replace it instead, for example with one built by
+//! an OpenTelemetry propagator.
+//!
+//! [`WusulConfig::with_trace_context`]: crate::types::WusulConfig::with_trace_context
+
+use crate::error::{Result, WusulError};
+use crate::http_client::Endpoint;
+use crate::transport::{HeaderMap, HeaderValue, HttpResponse};
+use rand::Rng;
+use std::time::Instant;
+use tracing::field::Empty;
+use tracing::Span;
+
+/// W3C Trace Context header
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// The caller's trace, continued by the `traceparent` header of an API call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    /// W3C trace ID; an all-zero ID is invalid and ignored
+    pub trace_id: u128,
+    /// Whether the caller's trace is sampled
+    pub sampled: bool,
+}
+
+/// Source of the trace context an API call continues
+///
+/// Asked once per call, on the task making it, so it can read the current
+/// span. Return `None` outside of a trace. Implemented for closures, e.g. one
+/// reading the OpenTelemetry context of `tracing::Span::current()`.
+pub trait TraceContextSource: Send + Sync {
+    fn current(&self) -> Option<TraceContext>;
+}
+
+impl<F> TraceContextSource for F
+where
+    F: Fn() -> Option<TraceContext> + Send + Sync,
+{
+    fn current(&self) -> Option<TraceContext> {
+        self()
+    }
+}
+
+/// The span covering one API call, across all of its attempts
+pub(crate) struct Operation {
+    pub span: Span,
+    trace_id: String,
+    sampled: bool,
+    started: Instant,
+}
+
+impl Operation {
+    pub fn start(endpoint: &Endpoint<'_>, source: Option<&dyn TraceContextSource>) -> Self {
+        let context = source
+            .and_then(TraceContextSource::current)
+            .filter(|context| context.trace_id != 0)
+            .unwrap_or_else(|| TraceContext {
+                trace_id: rand::thread_rng().gen_range(1..=u128::MAX),
+                sampled: false,
+            });
+        let trace_id = format!("{:032x}", context.trace_id);
+        let span = tracing::info_span!(
+            "wusul.request",
+            operation = endpoint.operation,
+            http.method = %endpoint.method,
+            http.route = endpoint.path_template,
+            http.status_code = Empty,
+            attempts = Empty,
+            latency_ms = Empty,
+            trace_id = %trace_id,
+            error = Empty,
+        );
+
+        Self {
+            span,
+            trace_id,
+            sampled: context.sampled,
+            started: Instant::now(),
+        }
+    }
+
+    /// Open the span for an attempt and set its `traceparent` header
+    pub fn start_attempt(&self, attempt: u32, headers: &mut HeaderMap) -> Attempt {
+        let span_id = format!("{:016x}", rand::thread_rng().gen_range(1..=u64::MAX));
+        let flags = if self.sampled { "01" } else { "00" };
+        let traceparent = format!("00-{}-{}-{}", self.trace_id, span_id, flags);
+        if let Ok(value) = HeaderValue::from_str(&traceparent) {
+            headers.insert(TRACEPARENT_HEADER, value);
+        }
+
+        let span = tracing::info_span!(
+            parent: &self.span,
+            "wusul.attempt",
+            attempt,
+            span_id = %span_id,
+            http.status_code = Empty,
+            latency_ms = Empty,
+            error = Empty,
+        );
+
+        Attempt {
+            span,
+            started: Instant::now(),
+        }
+    }
+
+    /// Record the outcome of the call after its last attempt
+    pub fn finish(&self, attempts: u32, error: Option<&WusulError>) {
+        self.span.record("attempts", attempts);
+        self.span
+            .record("latency_ms", self.started.elapsed().as_millis() as u64);
+        if let Some(error) = error {
+            self.span.record("error", tracing::field::display(error));
+        }
+    }
+}
+
+/// The span covering a single attempt
+pub(crate) struct Attempt {
+    pub span: Span,
+    started: Instant,
+}
+
+impl Attempt {
+    /// Record what the transport returned for this attempt
+    pub fn finish(self, operation: &Operation, outcome: &Result<HttpResponse>) {
+        self.span
+            .record("latency_ms", self.started.elapsed().as_millis() as u64);
+        match outcome {
+            Ok(response) => {
+                let status = response.status.as_u16();
+                self.span.record("http.status_code", status);
+                operation.span.record("http.status_code", status);
+            }
+            Err(error) => {
+                self.span.record("error", tracing::field::display(error));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::Method;
+
+    #[test]
+    fn test_traceparent_format() {
+        let endpoint = Endpoint::new("test", Method::GET, "/health");
+        let operation = Operation::start(&endpoint, None);
+        let mut headers = HeaderMap::new();
+        operation.start_attempt(1, &mut headers);
+
+        let traceparent = headers[TRACEPARENT_HEADER].to_str().unwrap();
+        let parts: Vec<&str> = traceparent.split('-').collect();
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[0], "00");
+        assert_eq!(parts[1], operation.trace_id);
+        assert_eq!(parts[1].len(), 32);
+        assert_eq!(parts[2].len(), 16);
+        assert_eq!(parts[3], "00");
+    }
+
+    #[test]
+    fn test_traceparent_continues_caller_trace() {
+        let endpoint = Endpoint::new("test", Method::GET, "/health");
+        let source = || {
+            Some(TraceContext {
+                trace_id: 0x4bf92f3577b34da6a3ce929d0e0e4736,
+                sampled: true,
+            })
+        };
+        let operation = Operation::start(&endpoint, Some(&source));
+        let mut headers = HeaderMap::new();
+        operation.start_attempt(1, &mut headers);
+
+        let traceparent = headers[TRACEPARENT_HEADER].to_str().unwrap();
+        assert!(traceparent.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"));
+        assert!(traceparent.ends_with("-01"));
+    }
+
+    #[test]
+    fn test_invalid_caller_trace_falls_back_to_random() {
+        let endpoint = Endpoint::new("test", Method::GET, "/health");
+        let source = || {
+            Some(TraceContext {
+                trace_id: 0,
+                sampled: true,
+            })
+        };
+        let operation = Operation::start(&endpoint, Some(&source));
+
+        assert_ne!(operation.trace_id, format!("{:032x}", 0));
+        assert!(!operation.sampled);
+    }
+
+    #[test]
+    fn test_attempts_share_trace_id() {
+        let endpoint = Endpoint::new("test", Method::GET, "/health");
+        let operation = Operation::start(&endpoint, None);
+        let mut first = HeaderMap::new();
+        let mut second = HeaderMap::new();
+        operation.start_attempt(1, &mut first);
+        operation.start_attempt(2, &mut second);
+
+        let first = first[TRACEPARENT_HEADER].to_str().unwrap();
+        let second = second[TRACEPARENT_HEADER].to_str().unwrap();
+        assert_eq!(first[..35], second[..35]);
+        assert_ne!(first, second);
+    }
+}
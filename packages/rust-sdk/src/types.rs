@@ -1,23 +1,80 @@
+use crate::auth::SignatureVersion;
+use crate::circuit_breaker::CircuitBreaker;
+use crate::error::{ErrorBody, Result};
+use crate::middleware::Middleware;
+use crate::rate_limit::{RateLimitInfo, RateLimiter};
+use crate::retry::RetryPolicy;
+use crate::secret::{SecretProvider, SecretString};
+#[cfg(feature = "tracing")]
+use crate::telemetry::TraceContextSource;
+use crate::transport::{
+    parse_header, ClientIdentity, HeaderMap, ProxyConfig, StatusCode, Transport,
+    DEFAULT_MAX_RESPONSE_SIZE,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 
 /// Configuration for the Wusul client
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct WusulConfig {
     pub account_id: String,
-    pub shared_secret: String,
+    /// Shared secret used to sign requests unless `secret_provider` is set
+    pub shared_secret: SecretString,
+    /// Source of the shared secret, asked on every request
+    pub secret_provider: Option<Arc<dyn SecretProvider>>,
     pub base_url: String,
     pub timeout: std::time::Duration,
+    /// Policy for retrying failed requests
+    pub retry_policy: RetryPolicy,
+    /// Default `Accept-Language` of every request, e.g. `ar`; none is sent when unset
+    pub locale: Option<String>,
+    /// Request signing scheme; v1 unless set
+    pub signature_version: SignatureVersion,
+    /// Transport used to send requests; `reqwest` with `timeout` applied when unset
+    pub transport: Option<Arc<dyn Transport>>,
+    /// Middleware wrapping every request attempt, outermost first
+    pub middleware: Vec<Arc<dyn Middleware>>,
+    /// Client-side rate limiter; requests are not limited when unset
+    pub rate_limiter: Option<RateLimiter>,
+    /// Circuit breaker that fails requests fast during outages; disabled when unset
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// HTTP proxy for the default transport
+    pub proxy: Option<ProxyConfig>,
+    /// Extra trusted root certificates (PEM) for the default transport
+    pub root_certificates: Vec<Vec<u8>>,
+    /// Client certificate and key for mutual TLS with the default transport
+    pub client_identity: Option<ClientIdentity>,
+    /// Largest response body, in bytes, the default transport accepts
+    pub max_response_size: usize,
+    /// Source of the caller's trace context for the `traceparent` header
+    #[cfg(feature = "tracing")]
+    pub trace_context: Option<Arc<dyn TraceContextSource>>,
 }
 
 impl WusulConfig {
     pub fn new(account_id: String, shared_secret: String) -> Self {
         Self {
             account_id,
-            shared_secret,
+            shared_secret: SecretString::new(shared_secret),
+            secret_provider: None,
             base_url: "https://api.wusul.io".to_string(),
             timeout: std::time::Duration::from_secs(30),
+            retry_policy: RetryPolicy::default(),
+            locale: None,
+            signature_version: SignatureVersion::default(),
+            transport: None,
+            middleware: Vec::new(),
+            rate_limiter: None,
+            circuit_breaker: None,
+            proxy: None,
+            root_certificates: Vec::new(),
+            client_identity: None,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            #[cfg(feature = "tracing")]
+            trace_context: None,
         }
     }
 
@@ -30,11 +87,229 @@ impl WusulConfig {
         self.timeout = timeout;
         self
     }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Ask the API for messages in `locale`, e.g. `ar` or `en`
+    ///
+    /// Sent as the `Accept-Language` header of every request; a per-request
+    /// [`RequestOptions::with_accept_language`] takes precedence. Whether error
+    /// messages come back localized depends on the API, see
+    /// [`WusulError::server_message`].
+    ///
+    /// [`WusulError::server_message`]: crate::error::WusulError::server_message
+    pub fn with_locale(mut self, locale: String) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Sign requests with `version` of the signing scheme
+    ///
+    /// v2 signs the method, path, a timestamp and a nonce along with the body
+    /// using HMAC-SHA256. Only enable it for servers that verify v2
+    /// signatures; see [`crate::auth`].
+    pub fn with_signature_version(mut self, version: SignatureVersion) -> Self {
+        self.signature_version = version;
+        self
+    }
+
+    /// Take the shared secret from `provider` instead of `shared_secret`
+    ///
+    /// The provider is asked on every request, so a rotated secret is used
+    /// without rebuilding the client. See [`crate::secret`].
+    pub fn with_secret_provider<P: SecretProvider + 'static>(mut self, provider: P) -> Self {
+        self.secret_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Send requests through a custom transport instead of the default `reqwest` client
+    ///
+    /// The configured `timeout` does not apply to custom transports.
+    pub fn with_transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Send requests through an HTTP proxy
+    ///
+    /// Applies to the default transport only. Without it, the `HTTP_PROXY`,
+    /// `HTTPS_PROXY` and `NO_PROXY` environment variables are honoured.
+    pub fn with_proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Trust the root certificates in `pem` in addition to the built-in ones
+    ///
+    /// `pem` may contain several certificates. Applies to the default transport
+    /// only and needs the `rustls` or `native-tls` feature.
+    pub fn with_root_certificate_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates.push(pem.into());
+        self
+    }
+
+    /// Present a client certificate for mutual TLS
+    ///
+    /// Applies to the default transport only and needs the `rustls` or
+    /// `native-tls` feature.
+    pub fn with_client_identity(mut self, identity: ClientIdentity) -> Self {
+        self.client_identity = Some(identity);
+        self
+    }
+
+    /// Fail responses whose body is larger than `max_response_size` bytes
+    ///
+    /// Guards against a misbehaving server or proxy streaming an unbounded
+    /// body. Applies to the default transport only.
+    pub fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+
+    /// Append a middleware to the chain
+    ///
+    /// Middleware runs in registration order for requests and in reverse order
+    /// for responses.
+    pub fn with_middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Keep requests within the budget of `rate_limiter`
+    ///
+    /// Pass a clone of the same limiter to several configs to share its budget.
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Fail requests fast with `WusulError::CircuitOpen` while the API keeps failing
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Continue the caller's trace in the `traceparent` header of every request
+    ///
+    /// `source` is asked at the start of each call; without it, or when it
+    /// returns `None`, calls start a new unsampled trace. See
+    /// [`crate::telemetry`].
+    #[cfg(feature = "tracing")]
+    pub fn with_trace_context<S: TraceContextSource + 'static>(mut self, source: S) -> Self {
+        self.trace_context = Some(Arc::new(source));
+        self
+    }
+}
+
+impl fmt::Debug for WusulConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("WusulConfig");
+        debug
+            .field("account_id", &self.account_id)
+            .field("shared_secret", &self.shared_secret)
+            .field("secret_provider", &self.secret_provider.is_some())
+            .field("base_url", &self.base_url)
+            .field("timeout", &self.timeout)
+            .field("retry_policy", &self.retry_policy)
+            .field("locale", &self.locale)
+            .field("signature_version", &self.signature_version)
+            .field("custom_transport", &self.transport.is_some())
+            .field("middleware", &self.middleware.len())
+            .field("rate_limiter", &self.rate_limiter)
+            .field("circuit_breaker", &self.circuit_breaker)
+            .field("proxy", &self.proxy)
+            .field("root_certificates", &self.root_certificates.len())
+            .field("client_identity", &self.client_identity)
+            .field("max_response_size", &self.max_response_size);
+        #[cfg(feature = "tracing")]
+        debug.field("trace_context", &self.trace_context.is_some());
+        debug.finish()
+    }
+}
+
+/// Options that apply to a single request
+///
+/// Pass them to the `*_with_options` variant of a resource method, which
+/// returns an [`ApiResult`]. Options that are not set fall back to the
+/// client's configuration.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use wusul::retry::RetryPolicy;
+/// use wusul::RequestOptions;
+///
+/// # fn example() -> wusul::error::Result<()> {
+/// // Reuse the same key when repeating the call, e.g. from a job queue
+/// let options = RequestOptions::new().with_idempotency_key("issue-EMP001".to_string());
+///
+/// // Give a large upload more time, and don't retry it
+/// let options = RequestOptions::new()
+///     .with_timeout(Duration::from_secs(120))
+///     .with_retry_policy(RetryPolicy::none())
+///     .with_header("X-Request-Source", "template-sync")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestOptions {
+    /// Key sent in the `Idempotency-Key` header. Replaying a key within 24
+    /// hours makes the API return the original result of a `POST` instead of
+    /// performing the operation again; other methods ignore it. Generated
+    /// automatically for `POST` calls while retries are enabled, and reused
+    /// for every attempt.
+    pub idempotency_key: Option<String>,
+    /// Timeout for each attempt, instead of the configured `timeout`
+    pub timeout: Option<std::time::Duration>,
+    /// Retry policy for this call, instead of the configured one
+    pub retry_policy: Option<RetryPolicy>,
+    /// Value of the `Accept-Language` header, e.g. `fr-FR`
+    pub accept_language: Option<String>,
+    /// Extra headers. They cannot replace the authentication headers.
+    pub headers: HeaderMap,
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_idempotency_key(mut self, idempotency_key: String) -> Self {
+        self.idempotency_key = Some(idempotency_key);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Override the configured locale for this call
+    pub fn with_accept_language(mut self, accept_language: String) -> Self {
+        self.accept_language = Some(accept_language);
+        self
+    }
+
+    /// Add a header, checking that `name` and `value` are valid header text
+    pub fn with_header(mut self, name: &str, value: &str) -> Result<Self> {
+        let (name, value) = parse_header(name, value)?;
+        self.headers.insert(name, value);
+        Ok(self)
+    }
 }
 
 /// Platform type for digital wallets
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Platform {
     Apple,
     Google,
@@ -42,20 +317,21 @@ pub enum Platform {
 
 /// Protocol type for access control
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Protocol {
     Desfire,
     Seos,
-    #[serde(rename = "smart_tap")]
     SmartTap,
 }
 
 /// Use case type for card templates
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum UseCase {
     EmployeeBadge,
     Hotel,
+    Residential,
+    Vehicle,
 }
 
 /// Classification type for employees
@@ -70,8 +346,9 @@ pub enum Classification {
 
 /// State of an access pass
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum AccessPassState {
+    Pending,
     Active,
     Suspended,
     Unlinked,
@@ -79,22 +356,87 @@ pub enum AccessPassState {
     Expired,
 }
 
+/// Publishing status of a card template
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PublishStatus {
+    Draft,
+    Review,
+    Published,
+}
+
 /// Account tier level
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "UPPERCASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum AccountTier {
-    Basic,
-    Professional,
+    Starter,
+    Business,
     Enterprise,
 }
 
+/// Hotel tile data shown on hotel passes
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HotelTileData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_in_available_window_start_date_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_in_available_window_end_date_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_in_date_time: Option<String>,
+    #[serde(rename = "checkInURL", skip_serializing_if = "Option::is_none")]
+    pub check_in_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_checked_in: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_of_rooms_reserved: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_numbers: Option<Vec<String>>,
+}
+
+/// Hotel reservation details shown on hotel passes
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HotelReservations {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_in_date_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_checked_in: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_of_rooms_reserved: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property_location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property_map_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property_category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restaurant_voucher: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reservation_end_date_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reservation_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reservation_start_date_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_numbers: Option<Vec<String>>,
+}
+
 /// An access pass representing a digital credential
+///
+/// Different endpoints return different subsets of the fields, so everything
+/// except the ID and state is optional. The list endpoint returns camelCase
+/// keys, which are accepted as aliases.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "snake_case")]
 pub struct AccessPass {
+    #[serde(alias = "exId")]
     pub id: String,
-    pub card_template_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_template_id: Option<String>,
+    #[serde(alias = "employeeId", skip_serializing_if = "Option::is_none")]
     pub employee_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tag_id: Option<String>,
@@ -102,27 +444,37 @@ pub struct AccessPass {
     pub site_code: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub card_number: Option<String>,
-    pub full_name: String,
+    #[serde(alias = "fullName", skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub phone_number: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub classification: Option<Classification>,
-    pub start_date: String,
-    pub expiration_date: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(alias = "startDate", skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<DateTime<Utc>>,
+    #[serde(alias = "expirationDate", skip_serializing_if = "Option::is_none")]
+    pub expiration_date: Option<DateTime<Utc>>,
     pub state: AccessPassState,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub url: Option<String>,
+    pub install_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
+    #[serde(alias = "createdAt", skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(alias = "updatedAt", skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 /// Parameters for issuing a new access pass
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
+///
+/// Either `file_data` or both `site_code` and `card_number` must be provided.
+/// Dates must be ISO 8601 date-times, e.g. `2024-01-01T00:00:00Z`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub struct IssueAccessPassParams {
     pub card_template_id: String,
     pub full_name: String,
@@ -137,110 +489,213 @@ pub struct IssueAccessPassParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub card_number: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_data: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub phone_number: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub classification: Option<Classification>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub employee_photo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub membership_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_pass_ready_to_transact: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tile_data: Option<HotelTileData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reservations: Option<HotelReservations>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
-/// Parameters for listing access passes
-#[derive(Debug, Clone, Default, Serialize)]
-#[serde(rename_all = "camelCase")]
+/// Parameters for listing the access passes of a card template
+///
+/// The API lists passes per card template, so `card_template_id` is required
+/// and the type has no `Default`. Build it with [`new`](Self::new) and the
+/// `with_*` methods:
+///
+/// ```
+/// use wusul::types::{AccessPassState, ListAccessPassesParams};
+///
+/// let params = ListAccessPassesParams::new("template_123")
+///     .with_state(AccessPassState::Pending)
+///     .with_limit(25);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub struct ListAccessPassesParams {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub card_template_id: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub employee_id: Option<String>,
+    #[serde(rename = "template_id")]
+    pub card_template_id: String,
+    /// Only list passes in this state
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<AccessPassState>,
+    /// Page to return, starting at 1
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub limit: Option<u32>,
+    pub page: Option<u32>,
+    /// Passes per page; 50 unless set
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+impl ListAccessPassesParams {
+    /// List the access passes issued from the card template `card_template_id`
+    pub fn new(card_template_id: impl Into<String>) -> Self {
+        Self {
+            card_template_id: card_template_id.into(),
+            state: None,
+            page: None,
+            limit: None,
+        }
+    }
+
+    pub fn with_state(mut self, state: AccessPassState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    pub fn with_page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
 }
 
 /// Parameters for updating an access pass
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
+///
+/// `access_pass_id` selects the pass and is sent in the URL, not the body.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub struct UpdateAccessPassParams {
+    #[serde(skip)]
     pub access_pass_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub employee_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub full_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub email: Option<String>,
+    pub classification: Option<Classification>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub phone_number: Option<String>,
+    pub expiration_date: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub classification: Option<Classification>,
+    pub employee_photo: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub start_date: Option<String>,
+    pub title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub expiration_date: Option<String>,
+    pub file_data: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_pass_ready_to_transact: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tile_data: Option<HotelTileData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reservations: Option<HotelReservations>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
 /// Design configuration for a card template
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(rename_all = "camelCase")]
+///
+/// Colors are 6-digit hex strings (`#RRGGBB`); images are base64 encoded.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub struct CardTemplateDesign {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub background_color: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub foreground_color: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub label_color: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub logo_url: Option<String>,
+    pub label_secondary_color: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub hero_image_url: Option<String>,
+    pub background_image: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub strip_image_url: Option<String>,
+    pub logo_image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_image: Option<String>,
 }
 
 /// Support information for a card template
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub struct SupportInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub email: Option<String>,
+    pub support_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub support_phone_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub support_email: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub phone: Option<String>,
+    pub privacy_policy_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub website: Option<String>,
+    pub terms_and_conditions_url: Option<String>,
 }
 
 /// A card template for digital wallet passes
+///
+/// Different endpoints return different subsets of the fields, so everything
+/// except the ID is optional.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "snake_case")]
 pub struct CardTemplate {
     pub id: String,
-    pub name: String,
-    pub platform: Platform,
-    pub use_case: UseCase,
-    pub protocol: Protocol,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<Platform>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_case: Option<UseCase>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<Protocol>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_on_multiple_devices: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watch_count: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iphone_count: Option<u8>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub design: Option<CardTemplateDesign>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub support_info: Option<SupportInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub publish_status: Option<PublishStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_passes_count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 /// Parameters for creating a card template
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
+///
+/// `watch_count` and `iphone_count` (1-5) may only be set when
+/// `allow_on_multiple_devices` is true.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub struct CreateCardTemplateParams {
     pub name: String,
     pub platform: Platform,
     pub use_case: UseCase,
     pub protocol: Protocol,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_on_multiple_devices: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watch_count: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iphone_count: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub design: Option<CardTemplateDesign>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub support_info: Option<SupportInfo>,
@@ -249,13 +704,22 @@ pub struct CreateCardTemplateParams {
 }
 
 /// Parameters for updating a card template
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
+///
+/// `card_template_id` selects the template and is sent in the URL, not the body.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub struct UpdateCardTemplateParams {
+    #[serde(skip)]
     pub card_template_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_on_multiple_devices: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watch_count: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iphone_count: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub design: Option<CardTemplateDesign>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub support_info: Option<SupportInfo>,
@@ -263,34 +727,98 @@ pub struct UpdateCardTemplateParams {
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
-/// Parameters for reading event log
-#[derive(Debug, Clone, Default, Serialize)]
-#[serde(rename_all = "camelCase")]
+/// Device type recorded on event log entries
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Device {
+    Mobile,
+    Watch,
+}
+
+/// Event type filter accepted when reading the event log
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EventTypeFilter {
+    Issue,
+    Install,
+    Update,
+    Suspend,
+    Resume,
+    Unlink,
+}
+
+/// Type of an event recorded in the event log
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EventType {
+    AccessPassIssued,
+    AccessPassActivated,
+    AccessPassUpdated,
+    AccessPassSuspended,
+    AccessPassResumed,
+    AccessPassUnlinked,
+    AccessPassDeleted,
+    AccessPassExpired,
+    CardTemplateCreated,
+    CardTemplateUpdated,
+    CardTemplateRequestedPublishing,
+    CardTemplatePublished,
+    LandingPageCreated,
+    LandingPageUpdated,
+    LandingPageAttachedToTemplate,
+    CredentialProfileCreated,
+    CredentialProfileAttachedToTemplate,
+    /// An event type not known to this version of the SDK
+    #[serde(other)]
+    Unknown,
+}
+
+/// Parameters for reading the event log of a card template
+///
+/// `card_template_id` selects the template and is sent in the URL, not the query.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub struct ReadEventLogParams {
+    #[serde(skip)]
+    pub card_template_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub access_pass_id: Option<String>,
+    pub device: Option<Device>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub event_type: Option<String>,
+    pub event_type: Option<EventTypeFilter>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub start_date: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end_date: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u32>,
+}
+
+/// Summary of the access pass an event log entry refers to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct EventLogAccessPass {
+    pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub offset: Option<u32>,
+    pub full_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub employee_id: Option<String>,
 }
 
 /// An event log entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "snake_case")]
 pub struct EventLogEntry {
     pub id: String,
-    pub event_type: String,
-    pub access_pass_id: String,
-    pub timestamp: DateTime<Utc>,
+    pub event_type: EventType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<Device>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_pass: Option<EventLogAccessPass>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub created_at: DateTime<Utc>,
 }
 
 /// Standard API response for operations
@@ -300,3 +828,143 @@ pub struct ApiResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
 }
+
+/// Pagination information returned by list endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pagination {
+    pub page: u32,
+    pub limit: u32,
+    pub total: u64,
+    pub total_pages: u32,
+}
+
+/// Metadata attached to every Wusul API response
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
+    /// Any additional metadata fields sent by the server
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// The `{success, data, metadata, error}` envelope wrapping every API response body
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResponseEnvelope<T> {
+    pub success: bool,
+    pub data: Option<T>,
+    pub metadata: Option<ResponseMetadata>,
+    pub error: Option<ErrorBody>,
+}
+
+/// Decoded response data together with the envelope metadata
+#[derive(Debug, Clone)]
+pub struct WithMetadata<T> {
+    pub data: T,
+    pub metadata: ResponseMetadata,
+}
+
+impl<T> From<ApiResult<T>> for WithMetadata<T> {
+    fn from(result: ApiResult<T>) -> Self {
+        Self {
+            data: result.data,
+            metadata: result.meta.metadata,
+        }
+    }
+}
+
+/// Response headers kept on [`ResponseMeta::headers`]
+const META_HEADERS: &[&str] = &[
+    "x-request-id",
+    "content-language",
+    "date",
+    "ratelimit-limit",
+    "ratelimit-remaining",
+    "ratelimit-reset",
+    "retry-after",
+];
+
+/// Details of the HTTP response that a result was decoded from
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    /// HTTP status of the response
+    pub status: StatusCode,
+    /// Selected response headers: `X-Request-ID`, `Content-Language`, `Date`,
+    /// `Retry-After` and the `RateLimit-*` headers
+    pub headers: HeaderMap,
+    /// Request ID from the envelope metadata or the `X-Request-ID` header
+    pub request_id: Option<String>,
+    /// Server time the response was produced, from the envelope metadata
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Pagination of list responses
+    pub pagination: Option<Pagination>,
+    /// Parsed `RateLimit-*` headers
+    pub rate_limit: Option<RateLimitInfo>,
+    /// The complete envelope metadata, including fields the SDK does not know
+    pub metadata: ResponseMetadata,
+}
+
+impl ResponseMeta {
+    pub(crate) fn new(status: StatusCode, headers: &HeaderMap, metadata: ResponseMetadata) -> Self {
+        let mut kept = HeaderMap::new();
+        for name in META_HEADERS {
+            for value in headers.get_all(*name) {
+                kept.append(*name, value.clone());
+            }
+        }
+
+        let request_id = metadata.request_id.clone().or_else(|| {
+            headers
+                .get("x-request-id")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        });
+
+        Self {
+            status,
+            request_id,
+            timestamp: metadata.timestamp,
+            pagination: metadata.pagination,
+            rate_limit: RateLimitInfo::from_headers(headers),
+            headers: kept,
+            metadata,
+        }
+    }
+
+    /// The value of a selected response header, if present and valid text
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+}
+
+/// Decoded response data together with details of the response
+///
+/// Returned by the `*_with_options` resource methods.
+#[derive(Debug, Clone)]
+pub struct ApiResult<T> {
+    /// The decoded `data` of the response
+    pub data: T,
+    /// Status, headers and metadata of the response
+    pub meta: ResponseMeta,
+}
+
+impl<T> ApiResult<T> {
+    /// Discard the response details
+    pub fn into_data(self) -> T {
+        self.data
+    }
+
+    /// Transform the data, keeping the response details
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ApiResult<U> {
+        ApiResult {
+            data: f(self.data),
+            meta: self.meta,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::{config, error, ok};
+use serde_json::{json, Value};
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer};
+use wusul::error::{ErrorCode, WusulError};
+use wusul::retry::RetryPolicy;
+use wusul::types::AccessPassState;
+use wusul::{RequestOptions, Wusul};
+
+fn client(server: &MockServer) -> Wusul {
+    Wusul::with_config(config(server.uri()).with_retry_policy(RetryPolicy::none())).unwrap()
+}
+
+fn pass(id: &str) -> Value {
+    json!({
+        "id": id,
+        "card_template_id": "template_123",
+        "full_name": "John Doe",
+        "state": "ACTIVE",
+        "created_at": "2024-01-01T00:00:00Z"
+    })
+}
+
+async fn mount_pass(server: &MockServer, id: &str, delay: Duration) {
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/access-passes/{}", id)))
+        .respond_with(ok(pass(id)).set_delay(delay))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+async fn mount_not_found(server: &MockServer, id: &str) {
+    Mock::given(method("GET"))
+        .and(path(format!("/v1/access-passes/{}", id)))
+        .respond_with(error(404, "ACCESS_PASS_NOT_FOUND", "Access pass not found"))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_get_access_pass() {
+    let server = MockServer::start().await;
+    mount_pass(&server, "pass_123", Duration::ZERO).await;
+
+    let access_pass = client(&server).access_passes.get("pass_123").await.unwrap();
+
+    assert_eq!(access_pass.id, "pass_123");
+    assert_eq!(
+        access_pass.card_template_id.as_deref(),
+        Some("template_123")
+    );
+    assert_eq!(access_pass.full_name.as_deref(), Some("John Doe"));
+    assert_eq!(access_pass.state, AccessPassState::Active);
+
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests[0]
+        .url
+        .query_pairs()
+        .any(|(key, _)| key == "sig_payload"));
+}
+
+#[tokio::test]
+async fn test_get_unknown_access_pass() {
+    let server = MockServer::start().await;
+    mount_not_found(&server, "pass_missing").await;
+
+    let err = client(&server)
+        .access_passes
+        .get("pass_missing")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, WusulError::NotFound(_)));
+    assert_eq!(err.error_code(), Some(&ErrorCode::AccessPassNotFound));
+}
+
+#[tokio::test]
+async fn test_get_empty_id_is_rejected() {
+    let server = MockServer::start().await;
+
+    let err = client(&server).access_passes.get("").await.unwrap_err();
+
+    assert!(matches!(err, WusulError::InvalidParameter(_)));
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_get_id_is_a_single_path_segment() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes/pass%2F1%3Fstate%3DACTIVE"))
+        .respond_with(ok(pass("pass/1?state=ACTIVE")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let access_pass = client(&server)
+        .access_passes
+        .get("pass/1?state=ACTIVE")
+        .await
+        .unwrap();
+    assert_eq!(access_pass.id, "pass/1?state=ACTIVE");
+
+    let err = client(&server)
+        .access_passes
+        .suspend("pass_1/../../card-templates")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WusulError::NotFound(_)));
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(
+        requests[1].url.path(),
+        "/v1/access-passes/pass_1%2F..%2F..%2Fcard-templates/suspend"
+    );
+}
+
+#[tokio::test]
+async fn test_get_many_returns_results_in_order() {
+    let server = MockServer::start().await;
+    // The first pass answers last, so results must be reordered to match the input
+    mount_pass(&server, "pass_1", Duration::from_millis(100)).await;
+    mount_not_found(&server, "pass_missing").await;
+    mount_pass(&server, "pass_2", Duration::ZERO).await;
+
+    let results = client(&server)
+        .access_passes
+        .get_many_with_options(
+            ["pass_1", "pass_missing", "pass_2"],
+            &RequestOptions::default(),
+            2,
+        )
+        .await;
+
+    let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["pass_1", "pass_missing", "pass_2"]);
+    assert_eq!(results[0].1.as_ref().unwrap().id, "pass_1");
+    assert!(matches!(results[1].1, Err(WusulError::NotFound(_))));
+    assert_eq!(results[2].1.as_ref().unwrap().id, "pass_2");
+}
+
+#[tokio::test]
+async fn test_get_many_empty() {
+    let server = MockServer::start().await;
+
+    let results = client(&server)
+        .access_passes
+        .get_many(Vec::<String>::new())
+        .await;
+
+    assert!(results.is_empty());
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use base64::{engine::general_purpose, Engine as _};
+use common::{client, ok, ACCOUNT_ID, SHARED_SECRET};
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, Request};
+use wusul::auth::verify_signature;
+use wusul::types::{AccessPassState, ListAccessPassesParams};
+
+fn query_value(request: &Request, key: &str) -> Option<String> {
+    request
+        .url
+        .query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.into_owned())
+}
+
+fn header(request: &Request, name: &str) -> String {
+    request
+        .headers
+        .get(name)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_get_sends_signed_sig_payload() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .and(query_param("template_id", "template_123"))
+        .and(query_param("state", "ACTIVE"))
+        .respond_with(ok(json!({"items": []})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = ListAccessPassesParams::new("template_123").with_state(AccessPassState::Active);
+    client(&server).access_passes.list(params).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let request = &requests[0];
+    let sig_payload = query_value(request, "sig_payload").expect("sig_payload missing");
+
+    assert_eq!(header(request, "X-ACCT-ID"), ACCOUNT_ID);
+    assert!(verify_signature(
+        SHARED_SECRET,
+        &sig_payload,
+        &header(request, "X-PAYLOAD-SIG")
+    ));
+
+    let decoded = general_purpose::STANDARD.decode(&sig_payload).unwrap();
+    let decoded: Value = serde_json::from_slice(&decoded).unwrap();
+    assert_eq!(
+        decoded,
+        json!({"template_id": "template_123", "state": "ACTIVE"})
+    );
+}
+
+#[tokio::test]
+async fn test_get_without_params_sends_sig_payload() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ok(json!({"status": "healthy"})))
+        .mount(&server)
+        .await;
+
+    let health = client(&server).health().await.unwrap();
+    assert_eq!(health["status"], "healthy");
+
+    let requests = server.received_requests().await.unwrap();
+    let sig_payload = query_value(&requests[0], "sig_payload").unwrap();
+    assert!(verify_signature(
+        SHARED_SECRET,
+        &sig_payload,
+        &header(&requests[0], "X-PAYLOAD-SIG")
+    ));
+}
@@ -0,0 +1,183 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use common::{config, error, healthy, ok, unavailable, ACCOUNT_ID};
+use serde_json::json;
+use tokio::runtime::Runtime;
+use wiremock::matchers::{header, header_exists, method, path, query_param};
+use wiremock::{Mock, MockServer};
+use wusul::blocking::Wusul;
+use wusul::error::WusulError;
+use wusul::types::{AccessPassState, ListAccessPassesParams};
+
+/// A mock server driven by its own runtime, so the test itself stays synchronous
+struct Server {
+    runtime: Runtime,
+    server: MockServer,
+}
+
+impl Server {
+    fn start() -> Self {
+        let runtime = Runtime::new().unwrap();
+        let server = runtime.block_on(MockServer::start());
+        Self { runtime, server }
+    }
+
+    fn mount(&self, mock: Mock) {
+        self.runtime.block_on(mock.mount(&self.server));
+    }
+
+    fn client(&self) -> Wusul {
+        Wusul::with_config(config(self.server.uri())).unwrap()
+    }
+}
+
+#[test]
+fn test_blocking_suspend() {
+    let server = Server::start();
+    server.mount(
+        Mock::given(method("POST"))
+            .and(path("/v1/access-passes/pass_123/suspend"))
+            .and(header("X-ACCT-ID", ACCOUNT_ID))
+            .and(header_exists("X-PAYLOAD-SIG"))
+            .respond_with(ok(json!({"id": "pass_123", "state": "SUSPENDED"})))
+            .expect(1),
+    );
+
+    let pass = server.client().access_passes.suspend("pass_123").unwrap();
+
+    assert_eq!(pass.id, "pass_123");
+    assert_eq!(pass.state, AccessPassState::Suspended);
+}
+
+#[test]
+fn test_blocking_list() {
+    let server = Server::start();
+    server.mount(
+        Mock::given(method("GET"))
+            .and(path("/v1/access-passes"))
+            .and(query_param("template_id", "template_123"))
+            .and(header_exists("X-PAYLOAD-SIG"))
+            .respond_with(ok(json!({"items": [
+                {"id": "pass_1", "state": "ACTIVE"},
+                {"id": "pass_2", "state": "SUSPENDED"}
+            ]}))),
+    );
+
+    let params = ListAccessPassesParams::new("template_123");
+    let passes = server.client().access_passes.list(params).unwrap();
+
+    assert_eq!(passes.len(), 2);
+}
+
+#[test]
+fn test_blocking_get_many() {
+    let server = Server::start();
+    server.mount(
+        Mock::given(method("GET"))
+            .and(path("/v1/access-passes/pass_1"))
+            .respond_with(ok(json!({"id": "pass_1", "state": "ACTIVE"}))),
+    );
+    server.mount(
+        Mock::given(method("GET"))
+            .and(path("/v1/access-passes/pass_404"))
+            .respond_with(error(404, "ACCESS_PASS_NOT_FOUND", "Access pass not found")),
+    );
+
+    let results = server
+        .client()
+        .access_passes
+        .get_many(["pass_1", "pass_404"]);
+
+    assert_eq!(results[0].0, "pass_1");
+    assert_eq!(
+        results[0].1.as_ref().unwrap().state,
+        AccessPassState::Active
+    );
+    assert_eq!(results[1].0, "pass_404");
+    assert!(matches!(results[1].1, Err(WusulError::NotFound(_))));
+}
+
+#[test]
+fn test_blocking_list_all() {
+    let server = Server::start();
+    for page in 1..=2u32 {
+        server.mount(
+            Mock::given(method("GET"))
+                .and(path("/v1/access-passes"))
+                .and(query_param("page", page.to_string()))
+                .respond_with(ok(json!({
+                    "items": [{"id": format!("pass_{}", page), "state": "ACTIVE"}],
+                    "pagination": {"page": page, "limit": 1, "total": 2, "totalPages": 2}
+                }))),
+        );
+    }
+
+    let params = ListAccessPassesParams::new("template_123").with_limit(1);
+    let ids: Vec<String> = server
+        .client()
+        .access_passes
+        .list_all(params)
+        .map(|pass| pass.unwrap().id)
+        .collect();
+
+    assert_eq!(ids, ["pass_1", "pass_2"]);
+}
+
+#[test]
+fn test_blocking_console_errors() {
+    let server = Server::start();
+    server.mount(
+        Mock::given(method("GET"))
+            .and(path("/v1/console/card-templates/template_404"))
+            .respond_with(error(404, "NOT_FOUND", "Card template not found")),
+    );
+
+    let err = server
+        .client()
+        .console
+        .read_template("template_404")
+        .unwrap_err();
+
+    assert!(matches!(err, WusulError::NotFound(_)));
+    assert_eq!(err.code(), Some("NOT_FOUND"));
+}
+
+#[test]
+fn test_blocking_client_retries() {
+    let server = Server::start();
+    server.mount(
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(unavailable())
+            .up_to_n_times(1),
+    );
+    server.mount(
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(healthy()),
+    );
+
+    let health = server.client().health().unwrap();
+
+    assert_eq!(health["status"], "healthy");
+}
+
+#[test]
+fn test_blocking_client_is_shareable_across_threads() {
+    let server = Server::start();
+    server.mount(
+        Mock::given(method("POST"))
+            .and(path("/v1/access-passes/pass_123/resume"))
+            .respond_with(ok(json!({"id": "pass_123", "state": "ACTIVE"})))
+            .expect(4),
+    );
+
+    let client = server.client();
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| client.access_passes.resume("pass_123").unwrap());
+        }
+    });
+}
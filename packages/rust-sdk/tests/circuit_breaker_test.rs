@@ -0,0 +1,75 @@
+mod common;
+
+use common::{config, healthy, unavailable};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer};
+use wusul::circuit_breaker::{CircuitBreaker, CircuitState};
+use wusul::error::WusulError;
+use wusul::Wusul;
+
+fn client(server: &MockServer, breaker: &CircuitBreaker) -> Wusul {
+    Wusul::with_config(config(server.uri()).with_circuit_breaker(breaker.clone())).unwrap()
+}
+
+#[tokio::test]
+async fn test_open_circuit_fails_fast() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(unavailable())
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&changes);
+    let breaker = CircuitBreaker::new(3, Duration::from_secs(60))
+        .on_state_change(move |from, to| recorded.lock().unwrap().push((from, to)));
+    let client = client(&server, &breaker);
+
+    // Three attempts of the first call trip the breaker
+    let err = client.health().await.unwrap_err();
+    assert!(matches!(err, WusulError::ApiError { status: 503, .. }));
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    // Later calls are not sent at all
+    let err = client.health().await.unwrap_err();
+    assert!(matches!(err, WusulError::CircuitOpen { .. }));
+    assert!(err.retry_after().unwrap() <= Duration::from_secs(60));
+
+    assert_eq!(
+        *changes.lock().unwrap(),
+        vec![(CircuitState::Closed, CircuitState::Open)]
+    );
+}
+
+#[tokio::test]
+async fn test_circuit_closes_after_successful_trial() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(unavailable())
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(healthy())
+        .mount(&server)
+        .await;
+
+    let breaker = CircuitBreaker::new(1, Duration::from_millis(50));
+    let client = client(&server, &breaker);
+
+    // The retry after the first failure is rejected by the open breaker
+    let err = client.health().await.unwrap_err();
+    assert!(matches!(err, WusulError::CircuitOpen { .. }));
+
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    let health = client.health().await.unwrap();
+
+    assert_eq!(health["status"], "healthy");
+    assert_eq!(breaker.state(), CircuitState::Closed);
+}
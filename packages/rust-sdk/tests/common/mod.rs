@@ -0,0 +1,62 @@
+//! Fixtures shared by the integration tests
+//!
+//! Each test binary compiles this module on its own and uses only part of it.
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+use std::time::Duration;
+use wiremock::{MockServer, ResponseTemplate};
+use wusul::retry::RetryPolicy;
+use wusul::{Wusul, WusulConfig};
+
+pub const ACCOUNT_ID: &str = "0xabc1234567";
+pub const SHARED_SECRET: &str = "test_secret";
+
+/// Config for the test account against `base_url`, retrying with [`fast_retries`]
+pub fn config(base_url: String) -> WusulConfig {
+    WusulConfig::new(ACCOUNT_ID.to_string(), SHARED_SECRET.to_string())
+        .with_base_url(base_url)
+        .with_retry_policy(fast_retries())
+}
+
+/// Client for the test account against `server`, retrying with [`fast_retries`]
+pub fn client(server: &MockServer) -> Wusul {
+    Wusul::with_config(config(server.uri())).unwrap()
+}
+
+/// The default retry policy with a 1 ms base delay, so retrying tests stay fast
+pub fn fast_retries() -> RetryPolicy {
+    RetryPolicy::default().with_base_delay(Duration::from_millis(1))
+}
+
+/// A successful response envelope around `data`
+pub fn envelope(status: u16, data: Value) -> ResponseTemplate {
+    ResponseTemplate::new(status).set_body_json(json!({
+        "success": true,
+        "data": data,
+        "metadata": {"timestamp": "2024-01-01T00:00:00.000Z"}
+    }))
+}
+
+/// 200 response envelope around `data`
+pub fn ok(data: Value) -> ResponseTemplate {
+    envelope(200, data)
+}
+
+/// 200 response of the health endpoint
+pub fn healthy() -> ResponseTemplate {
+    ok(json!({"status": "healthy"}))
+}
+
+/// Error response envelope
+pub fn error(status: u16, code: &str, message: &str) -> ResponseTemplate {
+    ResponseTemplate::new(status).set_body_json(json!({
+        "success": false,
+        "error": {"code": code, "message": message}
+    }))
+}
+
+/// 503 response of an API that is down
+pub fn unavailable() -> ResponseTemplate {
+    error(503, "INTERNAL_ERROR", "Unavailable")
+}
@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+use std::time::Duration;
+use wusul::config::{ACCOUNT_ID_VAR, BASE_URL_VAR, SHARED_SECRET_VAR, TIMEOUT_VAR};
+use wusul::error::WusulError;
+use wusul::WusulConfig;
+
+const CREDENTIALS: &str = r#"
+[staging]
+account_id = "0xabc1234567"
+shared_secret = "staging_secret"
+base_url = "https://api.staging.wusul.io"
+
+[prod]
+account_id = "0xdef7654321"
+shared_secret = "prod_secret"
+timeout = 10
+"#;
+
+/// Write `contents` to a credentials file unique to the calling test
+fn credentials_file(test: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "wusul-credentials-{}-{}.toml",
+        std::process::id(),
+        test
+    ));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn config_error(result: Result<WusulConfig, WusulError>) -> String {
+    match result {
+        Err(WusulError::ConfigError(msg)) => msg,
+        other => panic!("Expected ConfigError, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_from_profile_file() {
+    let path = credentials_file("profiles", CREDENTIALS);
+
+    let staging = WusulConfig::from_profile_file(&path, "staging").unwrap();
+    assert_eq!(staging.account_id, "0xabc1234567");
+    assert_eq!(staging.shared_secret.expose_secret(), "staging_secret");
+    assert_eq!(staging.base_url, "https://api.staging.wusul.io");
+    assert_eq!(staging.timeout, Duration::from_secs(30));
+
+    let prod = WusulConfig::from_profile_file(&path, "prod").unwrap();
+    assert_eq!(prod.account_id, "0xdef7654321");
+    assert_eq!(prod.base_url, "https://api.wusul.io");
+    assert_eq!(prod.timeout, Duration::from_secs(10));
+}
+
+#[test]
+fn test_missing_profile_lists_available_profiles() {
+    let path = credentials_file("missing-profile", CREDENTIALS);
+
+    let msg = config_error(WusulConfig::from_profile_file(&path, "dev"));
+
+    assert!(msg.starts_with("Profile 'dev' not found in"), "{}", msg);
+    assert!(msg.ends_with("(available: prod, staging)"), "{}", msg);
+}
+
+#[test]
+fn test_missing_credentials_file() {
+    let msg = config_error(WusulConfig::from_profile_file(
+        "/nonexistent/wusul/credentials",
+        "staging",
+    ));
+
+    assert!(
+        msg.starts_with("Failed to read credentials file /nonexistent/wusul/credentials"),
+        "{}",
+        msg
+    );
+}
+
+#[test]
+fn test_malformed_credentials_file() {
+    let path = credentials_file("malformed", "[staging\naccount_id = \"0xabc\"\n");
+
+    let msg = config_error(WusulConfig::from_profile_file(&path, "staging"));
+
+    assert!(msg.starts_with("Invalid credentials file"), "{}", msg);
+}
+
+#[test]
+fn test_invalid_profile_fields() {
+    let path = credentials_file(
+        "invalid-fields",
+        r#"
+[no_secret]
+account_id = "0xabc1234567"
+
+[typo]
+account_id = "0xabc1234567"
+shared_secret = "secret"
+base_ur = "https://api.staging.wusul.io"
+
+[empty]
+account_id = ""
+shared_secret = "secret"
+
+[bad_timeout]
+account_id = "0xabc1234567"
+shared_secret = "secret"
+timeout = -1
+"#,
+    );
+
+    for (profile, expected) in [
+        ("no_secret", "missing field `shared_secret`"),
+        ("typo", "unknown field `base_ur`"),
+        ("empty", "account_id of profile 'empty' is empty"),
+        (
+            "bad_timeout",
+            "timeout of profile 'bad_timeout' must be a positive number of seconds, got '-1'",
+        ),
+    ] {
+        let msg = config_error(WusulConfig::from_profile_file(&path, profile));
+        assert!(msg.contains(expected), "{}: {}", profile, msg);
+    }
+}
+
+// The only test in this binary that touches the process environment
+#[test]
+fn test_from_env() {
+    std::env::set_var(ACCOUNT_ID_VAR, "0xabc1234567");
+    std::env::set_var(SHARED_SECRET_VAR, "env_secret");
+    std::env::set_var(BASE_URL_VAR, "https://api.staging.wusul.io");
+    std::env::set_var(TIMEOUT_VAR, "45");
+
+    let config = WusulConfig::from_env().unwrap();
+    assert_eq!(config.account_id, "0xabc1234567");
+    assert_eq!(config.shared_secret.expose_secret(), "env_secret");
+    assert_eq!(config.base_url, "https://api.staging.wusul.io");
+    assert_eq!(config.timeout, Duration::from_secs(45));
+
+    std::env::set_var(TIMEOUT_VAR, "45s");
+    let msg = config_error(WusulConfig::from_env());
+    assert_eq!(
+        msg,
+        "WUSUL_TIMEOUT must be a positive number of seconds, got '45s'"
+    );
+
+    std::env::remove_var(SHARED_SECRET_VAR);
+    let msg = config_error(WusulConfig::from_env());
+    assert_eq!(msg, "WUSUL_SHARED_SECRET is not set");
+}
@@ -0,0 +1,310 @@
+mod common;
+
+use common::{config, envelope};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+use wusul::error::{ErrorCode, WusulError};
+use wusul::retry::RetryPolicy;
+use wusul::types::{IssueAccessPassParams, UpdateAccessPassParams};
+use wusul::{RequestOptions, Wusul};
+
+/// Stand-in for the issue endpoint behind the API's idempotency middleware
+///
+/// A replayed key returns the pass created by the first request with that key,
+/// or 409 `IDEMPOTENCY_KEY_IN_USE` while that request is still being processed.
+/// The first response can be delayed to simulate a client timeout after the
+/// server already created the pass.
+#[derive(Clone, Default)]
+struct StandIn {
+    first_response_delay: Option<Duration>,
+    state: Arc<Mutex<StandInState>>,
+}
+
+#[derive(Default)]
+struct StandInState {
+    issued: usize,
+    /// The pass issued for each key, and when its response is sent
+    passes_by_key: HashMap<String, (Value, Instant)>,
+}
+
+impl StandIn {
+    fn issued(&self) -> usize {
+        self.state.lock().unwrap().issued
+    }
+}
+
+impl Respond for StandIn {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let key = request
+            .headers
+            .get("Idempotency-Key")
+            .map(|v| v.to_str().unwrap().to_string());
+        let mut state = self.state.lock().unwrap();
+
+        if let Some((pass, completed_at)) = key.as_ref().and_then(|k| state.passes_by_key.get(k)) {
+            if Instant::now() < *completed_at {
+                return ResponseTemplate::new(409).set_body_json(json!({
+                    "success": false,
+                    "error": {
+                        "code": "IDEMPOTENCY_KEY_IN_USE",
+                        "message": "A request with this idempotency key is still being processed"
+                    }
+                }));
+            }
+            return envelope(201, pass.clone());
+        }
+
+        state.issued += 1;
+        let body: Value = serde_json::from_slice(&request.body).unwrap();
+        let pass = json!({
+            "id": format!("pass_{}", state.issued),
+            "state": "PENDING",
+            "full_name": body["full_name"],
+            "card_template_id": body["card_template_id"]
+        });
+        let delay = match self.first_response_delay {
+            Some(delay) if state.issued == 1 => delay,
+            _ => Duration::ZERO,
+        };
+        if let Some(key) = key {
+            state
+                .passes_by_key
+                .insert(key, (pass.clone(), Instant::now() + delay));
+        }
+
+        envelope(201, pass).set_delay(delay)
+    }
+}
+
+async fn mount(server: &MockServer, stand_in: &StandIn) {
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes"))
+        .respond_with(stand_in.clone())
+        .mount(server)
+        .await;
+}
+
+fn issue_params() -> IssueAccessPassParams {
+    IssueAccessPassParams {
+        card_template_id: "template_123".to_string(),
+        full_name: "John Doe".to_string(),
+        start_date: "2024-01-01T00:00:00Z".to_string(),
+        expiration_date: "2024-12-31T23:59:59Z".to_string(),
+        employee_id: Some("EMP001".to_string()),
+        ..Default::default()
+    }
+}
+
+fn idempotency_keys(requests: &[Request]) -> Vec<Option<String>> {
+    requests
+        .iter()
+        .map(|r| {
+            r.headers
+                .get("Idempotency-Key")
+                .map(|v| v.to_str().unwrap().to_string())
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_replayed_key_returns_original_access_pass() {
+    let server = MockServer::start().await;
+    let stand_in = StandIn::default();
+    mount(&server, &stand_in).await;
+
+    let client =
+        Wusul::with_config(config(server.uri()).with_retry_policy(RetryPolicy::none())).unwrap();
+    let options = RequestOptions::new().with_idempotency_key("issue-EMP001".to_string());
+
+    let first = client
+        .access_passes
+        .issue_with_options(issue_params(), &options)
+        .await
+        .unwrap()
+        .data;
+    let replayed = client
+        .access_passes
+        .issue_with_options(issue_params(), &options)
+        .await
+        .unwrap()
+        .data;
+
+    assert_eq!(first.id, "pass_1");
+    assert_eq!(replayed.id, first.id);
+    assert_eq!(replayed.full_name.as_deref(), Some("John Doe"));
+    assert_eq!(stand_in.issued(), 1);
+
+    let other = client
+        .access_passes
+        .issue_with_options(
+            issue_params(),
+            &RequestOptions::new().with_idempotency_key("issue-EMP002".to_string()),
+        )
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(other.id, "pass_2");
+    assert_eq!(stand_in.issued(), 2);
+}
+
+#[tokio::test]
+async fn test_timed_out_issue_is_retried_with_generated_key() {
+    let server = MockServer::start().await;
+    let stand_in = StandIn {
+        first_response_delay: Some(Duration::from_millis(800)),
+        ..Default::default()
+    };
+    mount(&server, &stand_in).await;
+
+    let config = config(server.uri())
+        .with_timeout(Duration::from_millis(200))
+        .with_retry_policy(
+            RetryPolicy::default()
+                .with_max_attempts(6)
+                .with_base_delay(Duration::from_millis(100)),
+        );
+    let client = Wusul::with_config(config).unwrap();
+
+    // The first attempt creates the pass but times out; retries get 409 until
+    // it has finished, then its stored result
+    let pass = client.access_passes.issue(issue_params()).await.unwrap();
+
+    assert_eq!(pass.id, "pass_1");
+    assert_eq!(stand_in.issued(), 1);
+
+    let keys = idempotency_keys(&server.received_requests().await.unwrap());
+    assert!(keys.len() > 2, "{:?}", keys);
+    assert!(keys[0].is_some());
+    assert!(keys.iter().all(|key| *key == keys[0]));
+}
+
+#[tokio::test]
+async fn test_outcome_unknown_error_carries_generated_key() {
+    let server = MockServer::start().await;
+    let stand_in = StandIn {
+        first_response_delay: Some(Duration::from_millis(800)),
+        ..Default::default()
+    };
+    mount(&server, &stand_in).await;
+
+    let config = config(server.uri()).with_timeout(Duration::from_millis(200));
+    let client = Wusul::with_config(config).unwrap();
+
+    // The retries give up while the first attempt is still being processed
+    let err = client
+        .access_passes
+        .issue(issue_params())
+        .await
+        .unwrap_err();
+
+    let key = match &err {
+        WusulError::OutcomeUnknown {
+            idempotency_key,
+            source,
+        } => {
+            assert_eq!(source.error_code(), Some(&ErrorCode::IdempotencyKeyInUse));
+            idempotency_key.clone()
+        }
+        other => panic!("expected OutcomeUnknown, got {:?}", other),
+    };
+    assert_eq!(err.idempotency_key(), Some(key.as_str()));
+    assert!(err.is_retryable());
+
+    // Retrying later with the key returns the pass the first attempt issued
+    tokio::time::sleep(Duration::from_millis(800)).await;
+    let options = RequestOptions::new().with_idempotency_key(key);
+    let pass = client
+        .access_passes
+        .issue_with_options(issue_params(), &options)
+        .await
+        .unwrap()
+        .data;
+
+    assert_eq!(pass.id, "pass_1");
+    assert_eq!(stand_in.issued(), 1);
+}
+
+#[tokio::test]
+async fn test_caller_key_is_used_for_every_attempt() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_1/suspend"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_1/suspend"))
+        .respond_with(envelope(201, json!({"id": "pass_1", "state": "SUSPENDED"})))
+        .mount(&server)
+        .await;
+
+    let client = Wusul::with_config(config(server.uri())).unwrap();
+    let options = RequestOptions::new().with_idempotency_key("suspend-pass_1".to_string());
+
+    client
+        .access_passes
+        .suspend_with_options("pass_1", &options)
+        .await
+        .unwrap();
+
+    let keys = idempotency_keys(&server.received_requests().await.unwrap());
+    assert_eq!(
+        keys,
+        vec![
+            Some("suspend-pass_1".to_string()),
+            Some("suspend-pass_1".to_string())
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_no_key_without_retries() {
+    let server = MockServer::start().await;
+    let stand_in = StandIn::default();
+    mount(&server, &stand_in).await;
+
+    let client =
+        Wusul::with_config(config(server.uri()).with_retry_policy(RetryPolicy::none())).unwrap();
+    client.access_passes.issue(issue_params()).await.unwrap();
+
+    let keys = idempotency_keys(&server.received_requests().await.unwrap());
+    assert_eq!(keys, vec![None]);
+}
+
+#[tokio::test]
+async fn test_only_post_requests_get_a_generated_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "data": {"status": "healthy"}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/access-passes/pass_1"))
+        .respond_with(envelope(201, json!({"id": "pass_1", "state": "ACTIVE"})))
+        .mount(&server)
+        .await;
+
+    let client = Wusul::with_config(config(server.uri())).unwrap();
+    client.health().await.unwrap();
+    client
+        .access_passes
+        .update(UpdateAccessPassParams {
+            access_pass_id: "pass_1".to_string(),
+            full_name: Some("Jane Doe".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let keys = idempotency_keys(&server.received_requests().await.unwrap());
+    assert_eq!(keys, vec![None, None]);
+}
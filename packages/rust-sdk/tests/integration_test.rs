@@ -1,5 +1,5 @@
-use wusul::{Wusul, WusulConfig};
 use std::time::Duration;
+use wusul::{Wusul, WusulConfig};
 
 #[test]
 fn test_client_initialization() {
@@ -0,0 +1,146 @@
+mod common;
+
+use async_trait::async_trait;
+use common::{config, healthy};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wusul::error::{Result, WusulError};
+use wusul::middleware::{Middleware, Next, SetHeader};
+use wusul::retry::RetryPolicy;
+use wusul::transport::{HeaderValue, HttpRequest, HttpResponse, StatusCode};
+use wusul::Wusul;
+
+/// Numbers every attempt with a correlation ID and records what it observed
+#[derive(Clone, Default)]
+struct Observe {
+    attempts: Arc<AtomicUsize>,
+    seen: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Middleware for Observe {
+    async fn handle(&self, mut request: HttpRequest, next: Next<'_>) -> Result<HttpResponse> {
+        let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        request.headers.insert(
+            "X-Correlation-ID",
+            HeaderValue::from_str(&format!("corr-{}", attempt)).unwrap(),
+        );
+        assert!(request.headers.contains_key("X-PAYLOAD-SIG"));
+
+        let result = next.run(request).await;
+        let outcome = match &result {
+            Ok(response) => format!("status {}", response.status.as_u16()),
+            Err(err) => format!("error {}", err),
+        };
+        self.seen.lock().unwrap().push(outcome);
+        result
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_adds_headers() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .and(header("X-Tenant-ID", "tenant_42"))
+        .and(header("X-Correlation-ID", "corr-1"))
+        .respond_with(healthy())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let observe = Observe::default();
+    let config = config(server.uri())
+        .with_middleware(SetHeader::new("X-Tenant-ID", "tenant_42").unwrap())
+        .with_middleware(observe.clone());
+
+    Wusul::with_config(config).unwrap().health().await.unwrap();
+
+    assert_eq!(*observe.seen.lock().unwrap(), vec!["status 200"]);
+}
+
+#[tokio::test]
+async fn test_middleware_wraps_every_attempt() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .and(header("X-Correlation-ID", "corr-2"))
+        .respond_with(healthy())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let observe = Observe::default();
+    let client = Wusul::with_config(config(server.uri()).with_middleware(observe.clone())).unwrap();
+
+    client.health().await.unwrap();
+
+    // Responses reach the middleware before they are decoded into errors
+    assert_eq!(
+        *observe.seen.lock().unwrap(),
+        vec!["status 503", "status 200"]
+    );
+}
+
+#[tokio::test]
+async fn test_middleware_sees_transport_errors() {
+    let uri = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let observe = Observe::default();
+    let config = config(uri)
+        .with_retry_policy(RetryPolicy::none())
+        .with_middleware(observe.clone());
+
+    let err = Wusul::with_config(config)
+        .unwrap()
+        .health()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, WusulError::ConnectError(_)));
+    let seen = observe.seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    assert!(seen[0].starts_with("error"));
+}
+
+/// Answers health checks without touching the network
+struct ShortCircuit;
+
+#[async_trait]
+impl Middleware for ShortCircuit {
+    async fn handle(&self, request: HttpRequest, next: Next<'_>) -> Result<HttpResponse> {
+        if request.url.contains("/health") {
+            return Ok(HttpResponse {
+                status: StatusCode::OK,
+                headers: Default::default(),
+                body: r#"{"success": true, "data": {"status": "cached"}}"#.to_string(),
+            });
+        }
+        next.run(request).await
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_can_answer_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(healthy())
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = Wusul::with_config(config(server.uri()).with_middleware(ShortCircuit)).unwrap();
+    let health = client.health().await.unwrap();
+
+    assert_eq!(health["status"], "cached");
+}
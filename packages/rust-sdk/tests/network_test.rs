@@ -0,0 +1,191 @@
+mod common;
+
+use common::{config, healthy};
+use std::error::Error;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wusul::error::WusulError;
+use wusul::transport::ProxyConfig;
+use wusul::Wusul;
+
+#[tokio::test]
+async fn test_requests_go_through_proxy() {
+    let proxy = MockServer::start().await;
+    // "svc:secret" in base64
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .and(header("Proxy-Authorization", "Basic c3ZjOnNlY3JldA=="))
+        .respond_with(healthy())
+        .expect(1)
+        .mount(&proxy)
+        .await;
+
+    let proxy_config =
+        ProxyConfig::new(proxy.uri()).with_basic_auth("svc".to_string(), "secret".to_string());
+    let config = config("http://api.wusul.invalid".to_string()).with_proxy(proxy_config);
+
+    let health = Wusul::with_config(config).unwrap().health().await.unwrap();
+    assert_eq!(health["status"], "healthy");
+}
+
+#[tokio::test]
+async fn test_no_proxy_hosts_are_reached_directly() {
+    let api = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(healthy())
+        .expect(1)
+        .mount(&api)
+        .await;
+
+    // Nothing listens on the proxy port
+    let unreachable_proxy = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let proxy_config =
+        ProxyConfig::new(unreachable_proxy).with_no_proxy(vec!["127.0.0.1".to_string()]);
+    let config = config(api.uri()).with_proxy(proxy_config);
+
+    Wusul::with_config(config).unwrap().health().await.unwrap();
+}
+
+#[test]
+fn test_invalid_proxy_url() {
+    let config = config("https://api.wusul.io".to_string())
+        .with_proxy(ProxyConfig::new("not a url".to_string()));
+
+    let err = Wusul::with_config(config).err().unwrap();
+    assert!(matches!(err, WusulError::ConfigError(ref message) if message.contains("proxy")));
+}
+
+#[test]
+fn test_proxy_password_is_redacted() {
+    let proxy = ProxyConfig::new("http://proxy.internal:3128".to_string())
+        .with_basic_auth("svc".to_string(), "proxy_password".to_string());
+
+    let debug = format!(
+        "{:?}",
+        config("https://api.wusul.io".to_string()).with_proxy(proxy)
+    );
+    assert!(!debug.contains("proxy_password"));
+}
+
+/// Base URL of a port nothing listens on
+fn closed_port() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+#[tokio::test]
+async fn test_connection_refused_is_a_connect_error() {
+    let err = Wusul::with_config(config(closed_port()))
+        .unwrap()
+        .health()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, WusulError::ConnectError(_)));
+
+    // The I/O error is reachable through the source chain
+    let mut source = err.source();
+    let mut refused = false;
+    while let Some(cause) = source {
+        if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+            refused |= io.kind() == std::io::ErrorKind::ConnectionRefused;
+        }
+        source = cause.source();
+    }
+    assert!(refused);
+}
+
+#[tokio::test]
+async fn test_response_too_large() {
+    let api = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("x".repeat(2048)))
+        .mount(&api)
+        .await;
+
+    let config = config(api.uri()).with_max_response_size(1024);
+    let err = Wusul::with_config(config)
+        .unwrap()
+        .health()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, WusulError::ResponseTooLarge { limit: 1024 }));
+}
+
+#[cfg(any(feature = "rustls", feature = "native-tls"))]
+mod tls {
+    use super::*;
+    use wusul::transport::ClientIdentity;
+
+    const CA: &[u8] = include_bytes!("fixtures/tls/ca.pem");
+    const CLIENT_CERT: &[u8] = include_bytes!("fixtures/tls/client.pem");
+    const CLIENT_KEY: &[u8] = include_bytes!("fixtures/tls/client.key");
+
+    #[test]
+    fn test_root_certificate_and_client_identity() {
+        let config = config("https://api.staging.wusul.internal".to_string())
+            .with_root_certificate_pem(CA)
+            .with_client_identity(ClientIdentity::from_pem(CLIENT_CERT, CLIENT_KEY));
+
+        assert!(Wusul::with_config(config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_root_certificate() {
+        let config = config("https://api.wusul.io".to_string())
+            .with_root_certificate_pem("not a certificate");
+
+        let err = Wusul::with_config(config).err().unwrap();
+        assert!(
+            matches!(err, WusulError::ConfigError(ref message) if message.contains("root certificate"))
+        );
+    }
+
+    #[test]
+    fn test_invalid_client_identity() {
+        let config = config("https://api.wusul.io".to_string())
+            .with_client_identity(ClientIdentity::from_pem(CLIENT_CERT, "not a key"));
+
+        let err = Wusul::with_config(config).err().unwrap();
+        assert!(
+            matches!(err, WusulError::ConfigError(ref message) if message.contains("client identity"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tls_handshake_failure_is_a_tls_error() {
+        // A plain HTTP server cannot complete a TLS handshake
+        let api = MockServer::start().await;
+        let base_url = api.uri().replace("http://", "https://");
+
+        let err = Wusul::with_config(config(base_url))
+            .unwrap()
+            .health()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, WusulError::TlsError(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_private_key_is_redacted() {
+        let identity = ClientIdentity::from_pem(CLIENT_CERT, CLIENT_KEY);
+        assert!(!format!("{:?}", identity).contains("PRIVATE KEY"));
+    }
+}
+
+#[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+#[test]
+fn test_tls_settings_need_a_tls_backend() {
+    let config = config("https://api.wusul.io".to_string())
+        .with_root_certificate_pem(include_bytes!("fixtures/tls/ca.pem").as_slice());
+
+    let err = Wusul::with_config(config).err().unwrap();
+    assert!(matches!(err, WusulError::ConfigError(ref message) if message.contains("feature")));
+}
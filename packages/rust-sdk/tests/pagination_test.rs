@@ -0,0 +1,151 @@
+mod common;
+
+use common::{config, error, ok};
+use futures::{StreamExt, TryStreamExt};
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer};
+use wusul::error::WusulError;
+use wusul::retry::RetryPolicy;
+use wusul::types::{ListAccessPassesParams, Pagination, ReadEventLogParams};
+use wusul::{RequestOptions, Wusul};
+
+fn client(server: &MockServer) -> Wusul {
+    Wusul::with_config(config(server.uri()).with_retry_policy(RetryPolicy::none())).unwrap()
+}
+
+/// A page of two access passes out of five, two per page
+fn passes_page(page: u32) -> Value {
+    let ids: Vec<Value> = (0..2)
+        .map(|i| (page - 1) * 2 + i + 1)
+        .filter(|n| *n <= 5)
+        .map(|n| json!({"id": format!("pass_{}", n), "state": "ACTIVE"}))
+        .collect();
+    json!({
+        "items": ids,
+        "pagination": {"page": page, "limit": 2, "total": 5, "totalPages": 3}
+    })
+}
+
+async fn mount_passes_page(server: &MockServer, page: u32) {
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .and(query_param("page", page.to_string()))
+        .respond_with(ok(passes_page(page)))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+fn params() -> ListAccessPassesParams {
+    ListAccessPassesParams::new("template_123").with_limit(2)
+}
+
+#[tokio::test]
+async fn test_list_page_carries_pagination() {
+    let server = MockServer::start().await;
+    mount_passes_page(&server, 2).await;
+
+    let page = client(&server)
+        .access_passes
+        .list_page(params().with_page(2))
+        .await
+        .unwrap();
+
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(
+        page.pagination,
+        Some(Pagination {
+            page: 2,
+            limit: 2,
+            total: 5,
+            total_pages: 3
+        })
+    );
+    assert_eq!(page.total(), Some(5));
+    assert_eq!(page.next_page(), Some(3));
+}
+
+#[tokio::test]
+async fn test_list_all_walks_every_page() {
+    let server = MockServer::start().await;
+    for page in 1..=3 {
+        mount_passes_page(&server, page).await;
+    }
+
+    let passes: Vec<_> = client(&server)
+        .access_passes
+        .list_all(params())
+        .try_collect()
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = passes.iter().map(|pass| pass.id.as_str()).collect();
+    assert_eq!(ids, ["pass_1", "pass_2", "pass_3", "pass_4", "pass_5"]);
+}
+
+#[tokio::test]
+async fn test_list_all_stops_on_error() {
+    let server = MockServer::start().await;
+    mount_passes_page(&server, 1).await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .and(query_param("page", "2"))
+        .respond_with(error(500, "INTERNAL_ERROR", "Internal server error"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .and(query_param("page", "3"))
+        .respond_with(ok(passes_page(3)))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let results: Vec<_> = client(&server)
+        .access_passes
+        .list_all_with_options(params(), &RequestOptions::default(), 1)
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 3);
+    assert!(results[..2].iter().all(Result::is_ok));
+    assert!(matches!(
+        results[2],
+        Err(WusulError::ApiError { status: 500, .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_event_log_all_single_page() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/card-templates/template_123/logs"))
+        .and(query_param("page", "1"))
+        .respond_with(ok(json!({
+            "items": [{
+                "id": "event_1",
+                "event_type": "ACCESS_PASS_ISSUED",
+                "created_at": "2024-01-01T00:00:00.000Z"
+            }],
+            "pagination": {"page": 1, "limit": 100, "total": 1, "totalPages": 1}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = ReadEventLogParams {
+        card_template_id: "template_123".to_string(),
+        ..Default::default()
+    };
+    let events: Vec<_> = client(&server)
+        .console
+        .event_log_all(params)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].id, "event_1");
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{config, healthy};
+use std::time::{Duration, Instant};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer};
+use wusul::rate_limit::RateLimiter;
+use wusul::Wusul;
+
+fn client(server: &MockServer, limiter: &RateLimiter) -> Wusul {
+    Wusul::with_config(config(server.uri()).with_rate_limiter(limiter.clone())).unwrap()
+}
+
+#[tokio::test]
+async fn test_clones_share_the_budget() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(healthy())
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let limiter = RateLimiter::new(10, Duration::from_secs(3600));
+    let client = client(&server, &limiter);
+
+    let tasks: Vec<_> = (0..3)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.health().await.unwrap() })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    assert_eq!(limiter.available(), 7);
+}
+
+#[tokio::test]
+async fn test_budget_follows_rate_limit_headers() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            healthy()
+                .insert_header("RateLimit-Limit", "100")
+                .insert_header("RateLimit-Remaining", "4")
+                .insert_header("RateLimit-Reset", "900"),
+        )
+        .mount(&server)
+        .await;
+
+    let limiter = RateLimiter::api();
+    client(&server, &limiter).health().await.unwrap();
+
+    assert_eq!(limiter.available(), 4);
+}
+
+#[tokio::test]
+async fn test_exhausted_quota_holds_requests_until_reset() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            healthy()
+                .insert_header("RateLimit-Remaining", "0")
+                .insert_header("RateLimit-Reset", "1"),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(healthy())
+        .mount(&server)
+        .await;
+
+    let limiter = RateLimiter::api();
+    let client = client(&server, &limiter);
+    client.health().await.unwrap();
+
+    let started = Instant::now();
+    client.health().await.unwrap();
+
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
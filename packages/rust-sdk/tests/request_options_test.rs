@@ -0,0 +1,210 @@
+mod common;
+
+use common::{config, error, ok, unavailable, ACCOUNT_ID};
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wusul::error::WusulError;
+use wusul::retry::RetryPolicy;
+use wusul::{RequestOptions, Wusul};
+
+fn template() -> ResponseTemplate {
+    ok(json!({"id": "template_123", "publish_status": "PUBLISHED"}))
+}
+
+#[tokio::test]
+async fn test_extra_headers_and_accept_language() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/card-templates/template_123"))
+        .and(header("X-Request-Source", "template-sync"))
+        .and(header("Accept-Language", "fr-FR"))
+        .respond_with(template())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Wusul::with_config(config(server.uri())).unwrap();
+    let options = RequestOptions::new()
+        .with_accept_language("fr-FR".to_string())
+        .with_header("X-Request-Source", "template-sync")
+        .unwrap();
+
+    client
+        .console
+        .read_template_with_options("template_123", &options)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_extra_headers_cannot_replace_authentication() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/console/card-templates/template_123/publish"))
+        .and(header("X-ACCT-ID", ACCOUNT_ID))
+        .respond_with(template())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Wusul::with_config(config(server.uri())).unwrap();
+    let options = RequestOptions::new()
+        .with_header("X-ACCT-ID", "someone_else")
+        .unwrap();
+
+    client
+        .console
+        .publish_template_with_options("template_123", &options)
+        .await
+        .unwrap();
+}
+
+#[test]
+fn test_invalid_header_is_rejected() {
+    let err = RequestOptions::new()
+        .with_header("X-Request-Source", "line\nbreak")
+        .unwrap_err();
+    assert!(matches!(err, WusulError::InvalidParameter(_)));
+}
+
+#[tokio::test]
+async fn test_retry_policy_override() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/card-templates/template_123"))
+        .respond_with(unavailable())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Wusul::with_config(config(server.uri())).unwrap();
+    let options = RequestOptions::new().with_retry_policy(RetryPolicy::none());
+
+    let err = client
+        .console
+        .read_template_with_options("template_123", &options)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WusulError::ApiError { status: 503, .. }));
+}
+
+#[tokio::test]
+async fn test_no_generated_idempotency_key_without_retries() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/console/card-templates/template_123/publish"))
+        .respond_with(template())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Wusul::with_config(config(server.uri())).unwrap();
+    let options = RequestOptions::new().with_retry_policy(RetryPolicy::none());
+    client
+        .console
+        .publish_template_with_options("template_123", &options)
+        .await
+        .unwrap();
+
+    let received = server.received_requests().await.unwrap();
+    assert!(!received[0].headers.contains_key("Idempotency-Key"));
+}
+
+#[tokio::test]
+async fn test_timeout_override() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/console/card-templates/template_123/publish"))
+        .respond_with(template().set_delay(Duration::from_millis(300)))
+        .mount(&server)
+        .await;
+
+    let config = config(server.uri())
+        .with_timeout(Duration::from_millis(100))
+        .with_retry_policy(RetryPolicy::none());
+    let client = Wusul::with_config(config).unwrap();
+
+    // The configured timeout is too short for this call...
+    let err = client
+        .console
+        .publish_template("template_123")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WusulError::Timeout));
+
+    // ...but a longer per-call timeout lets it finish
+    let options = RequestOptions::new().with_timeout(Duration::from_secs(5));
+    let template = client
+        .console
+        .publish_template_with_options("template_123", &options)
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(template.id, "template_123");
+}
+
+#[tokio::test]
+async fn test_configured_locale_and_override() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/card-templates/template_ar"))
+        .and(header("Accept-Language", "ar"))
+        .respond_with(template())
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/card-templates/template_en"))
+        .and(header("Accept-Language", "en"))
+        .respond_with(template())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Wusul::with_config(config(server.uri()).with_locale("ar".to_string())).unwrap();
+
+    client.console.read_template("template_ar").await.unwrap();
+    client
+        .console
+        .read_template_with_options(
+            "template_en",
+            &RequestOptions::new().with_accept_language("en".to_string()),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_server_message_and_stable_code() {
+    // Stands in for a server that honours `Accept-Language`; the message is passed through as sent
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/card-templates/template_404"))
+        .and(header("Accept-Language", "ar"))
+        .respond_with(error(404, "NOT_FOUND", "لم يتم العثور على قالب البطاقة"))
+        .mount(&server)
+        .await;
+
+    let client = Wusul::with_config(config(server.uri()).with_locale("ar".to_string())).unwrap();
+    let err = client
+        .console
+        .read_template("template_404")
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.code(), Some("NOT_FOUND"));
+    assert_eq!(err.server_message(), Some("لم يتم العثور على قالب البطاقة"));
+    assert_eq!(WusulError::Timeout.server_message(), None);
+}
+
+#[test]
+fn test_invalid_locale_is_rejected() {
+    let config = config("https://api.wusul.io".to_string()).with_locale("ar\n".to_string());
+
+    assert!(matches!(
+        Wusul::with_config(config),
+        Err(WusulError::ConfigError(_))
+    ));
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::client;
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wusul::rate_limit::RateLimitInfo;
+use wusul::transport::StatusCode;
+use wusul::types::{IssueAccessPassParams, ListAccessPassesParams, Pagination};
+use wusul::RequestOptions;
+
+#[tokio::test]
+async fn test_list_with_options() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("RateLimit-Limit", "100")
+                .insert_header("RateLimit-Remaining", "42")
+                .insert_header("RateLimit-Reset", "600")
+                .insert_header("Content-Language", "ar")
+                .insert_header("X-Powered-By", "Express")
+                .set_body_json(json!({
+                    "success": true,
+                    "data": {
+                        "items": [{"id": "pass_1", "state": "ACTIVE"}],
+                        "pagination": {"page": 2, "limit": 1, "total": 5, "totalPages": 5}
+                    },
+                    "metadata": {
+                        "timestamp": "2024-01-01T00:00:00.000Z",
+                        "requestId": "req_123",
+                        "region": "me-central-1"
+                    }
+                })),
+        )
+        .mount(&server)
+        .await;
+
+    let response = client(&server)
+        .access_passes
+        .list_with_options(
+            ListAccessPassesParams::new("template_123"),
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.len(), 1);
+    let meta = &response.meta;
+    assert_eq!(meta.status, StatusCode::OK);
+    assert_eq!(meta.request_id.as_deref(), Some("req_123"));
+    assert_eq!(
+        meta.timestamp,
+        Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+    );
+    assert_eq!(
+        meta.pagination,
+        Some(Pagination {
+            page: 2,
+            limit: 1,
+            total: 5,
+            total_pages: 5
+        })
+    );
+    assert_eq!(
+        meta.rate_limit,
+        Some(RateLimitInfo {
+            limit: Some(100),
+            remaining: Some(42),
+            reset: Some(Duration::from_secs(600)),
+        })
+    );
+    assert_eq!(meta.header("Content-Language"), Some("ar"));
+    assert_eq!(meta.header("X-Powered-By"), None);
+    assert_eq!(meta.metadata.extra["region"], "me-central-1");
+}
+
+#[tokio::test]
+async fn test_issue_with_options() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes"))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .insert_header("X-Request-ID", "req_from_header")
+                .set_body_json(json!({
+                    "success": true,
+                    "data": {"id": "pass_123", "state": "PENDING"}
+                })),
+        )
+        .mount(&server)
+        .await;
+
+    let params = IssueAccessPassParams {
+        card_template_id: "template_123".to_string(),
+        full_name: "John Doe".to_string(),
+        start_date: "2024-01-01T00:00:00Z".to_string(),
+        expiration_date: "2024-12-31T23:59:59Z".to_string(),
+        ..Default::default()
+    };
+    let response = client(&server)
+        .access_passes
+        .issue_with_options(params, &RequestOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.id, "pass_123");
+    assert_eq!(response.meta.status, StatusCode::CREATED);
+    // Without a request ID in the metadata, the header is used
+    assert_eq!(response.meta.request_id.as_deref(), Some("req_from_header"));
+    assert_eq!(response.meta.timestamp, None);
+    assert_eq!(response.meta.rate_limit, None);
+}
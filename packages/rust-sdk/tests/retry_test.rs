@@ -0,0 +1,148 @@
+mod common;
+
+use common::{config, error, fast_retries, healthy};
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer};
+use wusul::error::WusulError;
+use wusul::retry::RetryPolicy;
+use wusul::Wusul;
+
+fn client(server: &MockServer, retry_policy: RetryPolicy) -> Wusul {
+    Wusul::with_config(config(server.uri()).with_retry_policy(retry_policy)).unwrap()
+}
+
+fn fast_policy() -> RetryPolicy {
+    fast_retries().with_max_delay(Duration::from_millis(50))
+}
+
+#[tokio::test]
+async fn test_get_retries_server_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(error(503, "INTERNAL_ERROR", "Something went wrong"))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(healthy())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let health = client(&server, fast_policy()).health().await.unwrap();
+    assert_eq!(health["status"], "healthy");
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_attempts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(error(500, "INTERNAL_ERROR", "Something went wrong"))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let err = client(&server, fast_policy()).health().await.unwrap_err();
+    assert!(matches!(err, WusulError::ApiError { status: 500, .. }));
+}
+
+#[tokio::test]
+async fn test_client_errors_are_not_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(error(400, "VALIDATION_ERROR", "Something went wrong"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server, fast_policy()).health().await.unwrap_err();
+    assert_eq!(err.code(), Some("VALIDATION_ERROR"));
+}
+
+#[tokio::test]
+async fn test_rate_limit_waits_for_server_delay() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            error(429, "RATE_LIMIT_EXCEEDED", "Something went wrong")
+                .insert_header("RateLimit-Remaining", "0")
+                .insert_header("RateLimit-Reset", "0"),
+        )
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(healthy())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let health = client(&server, fast_policy()).health().await.unwrap();
+    assert_eq!(health["status"], "healthy");
+}
+
+#[tokio::test]
+async fn test_rate_limit_surfaces_retry_after() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(
+            error(429, "RATE_LIMIT_EXCEEDED", "Something went wrong")
+                .insert_header("Retry-After", "900")
+                .insert_header("RateLimit-Remaining", "0")
+                .insert_header("RateLimit-Reset", "900"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // The server asks for a longer wait than the policy allows, so the error is returned
+    let err = client(&server, fast_policy()).health().await.unwrap_err();
+
+    assert!(matches!(err, WusulError::RateLimitExceeded { .. }));
+    assert_eq!(err.retry_after(), Some(Duration::from_secs(900)));
+    assert_eq!(err.code(), Some("RATE_LIMIT_EXCEEDED"));
+}
+
+#[tokio::test]
+async fn test_retries_disabled() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(error(503, "INTERNAL_ERROR", "Something went wrong"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server, RetryPolicy::none())
+        .health()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WusulError::ApiError { status: 503, .. }));
+}
+
+#[tokio::test]
+async fn test_connection_errors_are_retried() {
+    // Nothing listens on this port once the listener is dropped
+    let uri = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let config = config(uri).with_retry_policy(fast_policy().with_max_attempts(2));
+
+    let err = Wusul::with_config(config)
+        .unwrap()
+        .health()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WusulError::ConnectError(_)));
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use async_trait::async_trait;
+use common::{config, ok, ACCOUNT_ID};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer};
+use wusul::auth::create_auth_headers;
+use wusul::error::{Result, WusulError};
+use wusul::secret::{FileSecret, RotatingSecret, SecretProvider, SecretString};
+use wusul::{Wusul, WusulConfig};
+
+async fn server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/suspend"))
+        .respond_with(ok(json!({"id": "pass_123", "state": "SUSPENDED"})))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn client<P: SecretProvider + 'static>(server: &MockServer, provider: P) -> Wusul {
+    Wusul::with_config(config(server.uri()).with_secret_provider(provider)).unwrap()
+}
+
+/// Signatures of the requests the server received, in order
+async fn signatures(server: &MockServer) -> Vec<String> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| {
+            request.headers["X-PAYLOAD-SIG"]
+                .to_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect()
+}
+
+fn signed_with(secret: &str) -> String {
+    create_auth_headers(ACCOUNT_ID, secret, None).1
+}
+
+#[test]
+fn test_config_debug_redacts_the_secret() {
+    let config = WusulConfig::new(ACCOUNT_ID.to_string(), "hunter2".to_string());
+
+    let debug = format!("{:?}", config);
+
+    assert!(!debug.contains("hunter2"), "{}", debug);
+    assert!(debug.contains("[REDACTED]"));
+}
+
+#[tokio::test]
+async fn test_rotated_secret_is_used_without_rebuilding_the_client() {
+    let server = server().await;
+    let secret = RotatingSecret::new("first_secret");
+    let client = client(&server, secret.clone());
+
+    client.access_passes.suspend("pass_123").await.unwrap();
+    secret.rotate("second_secret");
+    client.access_passes.suspend("pass_123").await.unwrap();
+
+    assert_eq!(
+        signatures(&server).await,
+        vec![signed_with("first_secret"), signed_with("second_secret")]
+    );
+}
+
+#[tokio::test]
+async fn test_file_secret() {
+    let server = server().await;
+    let path = std::env::temp_dir().join(format!("wusul-secret-{}", std::process::id()));
+    std::fs::write(&path, "file_secret\n").unwrap();
+
+    client(&server, FileSecret::new(&path))
+        .access_passes
+        .suspend("pass_123")
+        .await
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(signatures(&server).await, vec![signed_with("file_secret")]);
+}
+
+#[tokio::test]
+async fn test_unreadable_secret_fails_before_sending() {
+    let server = server().await;
+
+    let err = client(&server, FileSecret::new("/nonexistent/wusul/secret"))
+        .access_passes
+        .suspend("pass_123")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, WusulError::ConfigError(_)));
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+/// A vault stand-in that counts how often it is asked
+struct Vault {
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl SecretProvider for Vault {
+    async fn shared_secret(&self) -> Result<SecretString> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(SecretString::new("vault_secret"))
+    }
+}
+
+#[tokio::test]
+async fn test_custom_provider_is_asked_per_request() {
+    let server = server().await;
+    let calls = Arc::new(AtomicUsize::new(0));
+    let client = client(
+        &server,
+        Vault {
+            calls: Arc::clone(&calls),
+        },
+    );
+
+    client.access_passes.suspend("pass_123").await.unwrap();
+    client.access_passes.suspend("pass_123").await.unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        signatures(&server).await,
+        vec![signed_with("vault_secret"), signed_with("vault_secret")]
+    );
+}
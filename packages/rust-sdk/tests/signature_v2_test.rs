@@ -0,0 +1,137 @@
+mod common;
+
+use common::{config, ok, unavailable, ACCOUNT_ID, SHARED_SECRET};
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request};
+use wusul::auth::{
+    SignatureVerifier, SignatureVersion, SignedRequest, NONCE_HEADER, SIGNATURE_HEADER,
+    SIGNATURE_VERSION_HEADER, TIMESTAMP_HEADER,
+};
+use wusul::types::ListAccessPassesParams;
+use wusul::Wusul;
+
+fn client(server: &MockServer, version: SignatureVersion) -> Wusul {
+    Wusul::with_config(config(server.uri()).with_signature_version(version)).unwrap()
+}
+
+fn header<'a>(request: &'a Request, name: &str) -> &'a str {
+    request.headers[name].to_str().unwrap()
+}
+
+/// Verify a received request the way a server would
+fn verify(verifier: &SignatureVerifier, request: &Request) -> wusul::error::Result<()> {
+    let path = match request.url.query() {
+        Some(query) => format!("{}?{}", request.url.path(), query),
+        None => request.url.path().to_string(),
+    };
+    let body = String::from_utf8(request.body.clone()).unwrap();
+
+    verifier.verify(
+        SHARED_SECRET,
+        &SignedRequest {
+            method: request.method.as_str(),
+            path: &path,
+            timestamp: header(request, TIMESTAMP_HEADER),
+            nonce: header(request, NONCE_HEADER),
+            body: &body,
+            signature: header(request, SIGNATURE_HEADER),
+        },
+    )
+}
+
+#[tokio::test]
+async fn test_v2_requests_verify_server_side() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/suspend"))
+        .respond_with(ok(json!({"id": "pass_123", "state": "SUSPENDED"})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/access-passes"))
+        .respond_with(ok(json!({"items": []})))
+        .mount(&server)
+        .await;
+
+    let client = client(&server, SignatureVersion::V2);
+    client.access_passes.suspend("pass_123").await.unwrap();
+    client
+        .access_passes
+        .list(ListAccessPassesParams::new("template_123"))
+        .await
+        .unwrap();
+
+    let verifier = SignatureVerifier::default();
+    let received = server.received_requests().await.unwrap();
+    assert_eq!(received.len(), 2);
+    for request in &received {
+        assert_eq!(header(request, SIGNATURE_VERSION_HEADER), "2");
+        verify(&verifier, request).unwrap();
+    }
+
+    // GET requests carry their parameters without a v1 sig_payload
+    let query = received[1].url.query().unwrap();
+    assert!(query.contains("template_id=template_123"));
+    assert!(!query.contains("sig_payload"));
+
+    // Delivering a request twice is a replay
+    assert!(verify(&verifier, &received[0]).is_err());
+}
+
+#[tokio::test]
+async fn test_v2_retries_are_signed_again() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/resume"))
+        .respond_with(unavailable())
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/resume"))
+        .respond_with(ok(json!({"id": "pass_123", "state": "ACTIVE"})))
+        .mount(&server)
+        .await;
+
+    client(&server, SignatureVersion::V2)
+        .access_passes
+        .resume("pass_123")
+        .await
+        .unwrap();
+
+    let received = server.received_requests().await.unwrap();
+    assert_eq!(received.len(), 2);
+    assert_ne!(
+        header(&received[0], NONCE_HEADER),
+        header(&received[1], NONCE_HEADER)
+    );
+    let verifier = SignatureVerifier::default();
+    for request in &received {
+        verify(&verifier, request).unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_v1_remains_the_default() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/suspend"))
+        .respond_with(ok(json!({"id": "pass_123", "state": "SUSPENDED"})))
+        .mount(&server)
+        .await;
+
+    client(&server, SignatureVersion::default())
+        .access_passes
+        .suspend("pass_123")
+        .await
+        .unwrap();
+
+    let received = server.received_requests().await.unwrap();
+    assert!(received[0].headers.get(SIGNATURE_VERSION_HEADER).is_none());
+    assert!(received[0].headers.get(NONCE_HEADER).is_none());
+    assert_eq!(
+        header(&received[0], SIGNATURE_HEADER),
+        wusul::auth::create_auth_headers(ACCOUNT_ID, SHARED_SECRET, None).1
+    );
+}
@@ -0,0 +1,92 @@
+//! Cross-language signature vectors shared with the Wusul API.
+//!
+//! `tests/fixtures/signature_vectors.json` was produced with the API's
+//! `encodePayload`/`generatePayloadSignature` (`apps/api/src/utils/auth.ts`),
+//! which verifies the same file in `src/utils/__tests__/signature-vectors.spec.ts`.
+//! Every vector's `canonical` body survives `JSON.stringify(JSON.parse(..))`
+//! unchanged, so the signature the SDK computes over the bytes it sends is the
+//! signature the API computes after re-encoding the parsed body.
+
+use serde::Deserialize;
+use wusul::auth::{create_auth_headers, create_signature, encode_payload, verify_signature};
+use wusul::canonical_json::to_canonical_string;
+
+#[derive(Deserialize)]
+struct VectorFile {
+    shared_secret: String,
+    vectors: Vec<Vector>,
+}
+
+#[derive(Deserialize)]
+struct Vector {
+    name: String,
+    input: String,
+    canonical: String,
+    sig_payload: String,
+    signature: String,
+}
+
+fn vectors() -> VectorFile {
+    let path = format!(
+        "{}/tests/fixtures/signature_vectors.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn test_canonical_encoding_matches_vectors() {
+    for vector in vectors().vectors {
+        let input: serde_json::Value = serde_json::from_str(&vector.input).unwrap();
+        assert_eq!(
+            to_canonical_string(&input),
+            vector.canonical,
+            "{}",
+            vector.name
+        );
+    }
+}
+
+#[test]
+fn test_canonical_encoding_is_stable() {
+    for vector in vectors().vectors {
+        let canonical: serde_json::Value = serde_json::from_str(&vector.canonical).unwrap();
+        assert_eq!(
+            to_canonical_string(&canonical),
+            vector.canonical,
+            "{}",
+            vector.name
+        );
+    }
+}
+
+#[test]
+fn test_signatures_match_vectors() {
+    let file = vectors();
+
+    for vector in file.vectors {
+        let input: serde_json::Value = serde_json::from_str(&vector.input).unwrap();
+
+        assert_eq!(
+            encode_payload(&input),
+            vector.sig_payload,
+            "{}",
+            vector.name
+        );
+        assert_eq!(
+            create_signature(&file.shared_secret, &vector.sig_payload),
+            vector.signature,
+            "{}",
+            vector.name
+        );
+        assert!(verify_signature(
+            &file.shared_secret,
+            &vector.sig_payload,
+            &vector.signature
+        ));
+
+        let (_, signature, body) = create_auth_headers("0xabc", &file.shared_secret, Some(&input));
+        assert_eq!(body, vector.canonical, "{}", vector.name);
+        assert_eq!(signature, vector.signature, "{}", vector.name);
+    }
+}
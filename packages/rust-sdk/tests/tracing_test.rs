@@ -0,0 +1,271 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use common::{client, config, error, ok, unavailable, SHARED_SECRET};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wusul::telemetry::{TraceContext, TRACEPARENT_HEADER};
+use wusul::Wusul;
+
+#[derive(Debug, Clone)]
+struct CapturedSpan {
+    name: &'static str,
+    parent: Option<u64>,
+    fields: HashMap<String, String>,
+}
+
+/// Layer that records every span and the values of its fields
+#[derive(Clone, Default)]
+struct Capture {
+    spans: Arc<Mutex<Vec<(u64, CapturedSpan)>>>,
+}
+
+impl Capture {
+    fn named(&self, name: &str) -> Vec<CapturedSpan> {
+        self.spans
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, span)| span.name == name)
+            .map(|(_, span)| span.clone())
+            .collect()
+    }
+
+    fn id_of(&self, name: &str) -> u64 {
+        self.spans
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(_, span)| span.name == name)
+            .map(|(id, _)| *id)
+            .unwrap()
+    }
+}
+
+struct Fields<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for Fields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Capture {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = HashMap::new();
+        attrs.record(&mut Fields(&mut fields));
+        let parent = ctx.span(id).and_then(|span| span.parent()).map(|p| p.id());
+        self.spans.lock().unwrap().push((
+            id.into_u64(),
+            CapturedSpan {
+                name: attrs.metadata().name(),
+                parent: parent.map(|p| p.into_u64()),
+                fields,
+            },
+        ));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        let mut spans = self.spans.lock().unwrap();
+        if let Some((_, span)) = spans.iter_mut().find(|(i, _)| *i == id.into_u64()) {
+            values.record(&mut Fields(&mut span.fields));
+        }
+    }
+}
+
+fn suspended() -> ResponseTemplate {
+    ok(json!({"id": "pass_123", "state": "SUSPENDED"}))
+}
+
+#[tokio::test]
+async fn test_request_span_records_operation() {
+    let capture = Capture::default();
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/suspend"))
+        .respond_with(suspended())
+        .mount(&server)
+        .await;
+
+    client(&server)
+        .access_passes
+        .suspend("pass_123")
+        .await
+        .unwrap();
+
+    let requests = capture.named("wusul.request");
+    assert_eq!(requests.len(), 1);
+    let fields = &requests[0].fields;
+    assert_eq!(fields["operation"], "access_passes.suspend");
+    assert_eq!(fields["http.method"], "POST");
+    assert_eq!(fields["http.route"], "/v1/access-passes/{id}/suspend");
+    assert_eq!(fields["http.status_code"], "200");
+    assert_eq!(fields["attempts"], "1");
+    assert!(fields.contains_key("latency_ms"));
+    assert!(!fields.contains_key("error"));
+
+    let attempts = capture.named("wusul.attempt");
+    assert_eq!(attempts.len(), 1);
+    assert_eq!(attempts[0].parent, Some(capture.id_of("wusul.request")));
+    assert_eq!(attempts[0].fields["attempt"], "1");
+    assert_eq!(attempts[0].fields["http.status_code"], "200");
+}
+
+#[tokio::test]
+async fn test_attempt_spans_for_retries() {
+    let capture = Capture::default();
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/card-templates/template_123"))
+        .respond_with(unavailable())
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/console/card-templates/template_123"))
+        .respond_with(ok(json!({"id": "template_123"})))
+        .mount(&server)
+        .await;
+
+    client(&server)
+        .console
+        .read_template("template_123")
+        .await
+        .unwrap();
+
+    let request = &capture.named("wusul.request")[0];
+    assert_eq!(request.fields["operation"], "console.read_template");
+    assert_eq!(
+        request.fields["http.route"],
+        "/v1/console/card-templates/{id}"
+    );
+    assert_eq!(request.fields["attempts"], "2");
+    assert_eq!(request.fields["http.status_code"], "200");
+
+    let attempts = capture.named("wusul.attempt");
+    let statuses: Vec<&str> = attempts
+        .iter()
+        .map(|a| a.fields["http.status_code"].as_str())
+        .collect();
+    assert_eq!(statuses, vec!["503", "200"]);
+
+    // Every attempt shares the trace ID and sends its own span ID as the parent
+    let trace_id = &request.fields["trace_id"];
+    let received = server.received_requests().await.unwrap();
+    assert_eq!(received.len(), 2);
+    for (request, attempt) in received.iter().zip(&attempts) {
+        let traceparent = request.headers[TRACEPARENT_HEADER].to_str().unwrap();
+        assert_eq!(
+            traceparent,
+            format!("00-{}-{}-00", trace_id, attempt.fields["span_id"])
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_traceparent_continues_caller_trace() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/suspend"))
+        .respond_with(suspended())
+        .mount(&server)
+        .await;
+
+    let config = config(server.uri()).with_trace_context(|| {
+        Some(TraceContext {
+            trace_id: 0x4bf92f3577b34da6a3ce929d0e0e4736,
+            sampled: true,
+        })
+    });
+    Wusul::with_config(config)
+        .unwrap()
+        .access_passes
+        .suspend("pass_123")
+        .await
+        .unwrap();
+
+    let received = server.received_requests().await.unwrap();
+    let traceparent = received[0].headers[TRACEPARENT_HEADER].to_str().unwrap();
+    assert!(traceparent.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"));
+    assert!(traceparent.ends_with("-01"));
+}
+
+#[tokio::test]
+async fn test_failed_request_records_error() {
+    let capture = Capture::default();
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/resume"))
+        .respond_with(error(404, "NOT_FOUND", "Access pass not found"))
+        .mount(&server)
+        .await;
+
+    let err = client(&server)
+        .access_passes
+        .resume("pass_123")
+        .await
+        .unwrap_err();
+
+    let request = &capture.named("wusul.request")[0];
+    assert_eq!(request.fields["http.status_code"], "404");
+    assert_eq!(request.fields["error"], err.to_string());
+}
+
+#[tokio::test]
+async fn test_secrets_are_never_recorded() {
+    let capture = Capture::default();
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/access-passes/pass_123/suspend"))
+        .respond_with(suspended())
+        .mount(&server)
+        .await;
+
+    client(&server)
+        .access_passes
+        .suspend("pass_123")
+        .await
+        .unwrap();
+
+    let received = server.received_requests().await.unwrap();
+    let signature = received[0].headers["X-PAYLOAD-SIG"].to_str().unwrap();
+
+    for (_, span) in capture.spans.lock().unwrap().iter() {
+        for (name, value) in &span.fields {
+            assert!(!name.to_lowercase().contains("sig"), "{} recorded", name);
+            assert!(!value.contains(signature), "signature recorded in {}", name);
+            assert!(
+                !value.contains(SHARED_SECRET),
+                "secret recorded in {}",
+                name
+            );
+        }
+    }
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use async_trait::async_trait;
+use common::{config, ACCOUNT_ID, SHARED_SECRET};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use wusul::auth::{create_signature, encode_payload};
+use wusul::error::{ErrorCode, FieldError, Result, WusulError};
+use wusul::retry::RetryPolicy;
+use wusul::transport::{HeaderMap, HttpRequest, HttpResponse, Method, StatusCode, Transport};
+use wusul::types::{AccessPassState, IssueAccessPassParams};
+use wusul::Wusul;
+
+/// In-memory transport that records requests and replays canned responses
+#[derive(Clone, Default)]
+struct FakeTransport {
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+    responses: Arc<Mutex<VecDeque<Result<HttpResponse>>>>,
+}
+
+impl FakeTransport {
+    fn respond(&self, status: StatusCode, body: Value) -> &Self {
+        self.responses.lock().unwrap().push_back(Ok(HttpResponse {
+            status,
+            headers: HeaderMap::new(),
+            body: body.to_string(),
+        }));
+        self
+    }
+
+    fn fail(&self, error: WusulError) -> &Self {
+        self.responses.lock().unwrap().push_back(Err(error));
+        self
+    }
+
+    fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Transport for FakeTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        self.requests.lock().unwrap().push(request);
+        self.responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("no response queued")
+    }
+}
+
+fn client(transport: &FakeTransport) -> Wusul {
+    let config = config("https://api.test".to_string())
+        .with_retry_policy(RetryPolicy::default().with_base_delay(std::time::Duration::ZERO))
+        .with_transport(transport.clone());
+    Wusul::with_config(config).unwrap()
+}
+
+fn header<'a>(request: &'a HttpRequest, name: &str) -> &'a str {
+    request.headers.get(name).unwrap().to_str().unwrap()
+}
+
+#[tokio::test]
+async fn test_custom_transport_receives_signed_requests() {
+    let transport = FakeTransport::default();
+    transport.respond(
+        StatusCode::CREATED,
+        json!({"success": true, "data": {"id": "pass_1", "state": "PENDING"}}),
+    );
+
+    let params = IssueAccessPassParams {
+        card_template_id: "template_123".to_string(),
+        full_name: "John Doe".to_string(),
+        start_date: "2024-01-01T00:00:00Z".to_string(),
+        expiration_date: "2024-12-31T23:59:59Z".to_string(),
+        ..Default::default()
+    };
+    let pass = client(&transport)
+        .access_passes
+        .issue(params.clone())
+        .await
+        .unwrap();
+    assert_eq!(pass.id, "pass_1");
+    assert_eq!(pass.state, AccessPassState::Pending);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(request.url, "https://api.test/v1/access-passes");
+    assert_eq!(header(request, "X-ACCT-ID"), ACCOUNT_ID);
+
+    let body: Value = serde_json::from_str(request.body.as_deref().unwrap()).unwrap();
+    assert_eq!(body, serde_json::to_value(&params).unwrap());
+    assert_eq!(
+        header(request, "X-PAYLOAD-SIG"),
+        create_signature(SHARED_SECRET, &encode_payload(&body))
+    );
+}
+
+#[tokio::test]
+async fn test_custom_transport_get_url_carries_query() {
+    let transport = FakeTransport::default();
+    transport.respond(
+        StatusCode::OK,
+        json!({"success": true, "data": {"status": "healthy"}}),
+    );
+
+    client(&transport).health().await.unwrap();
+
+    let request = &transport.requests()[0];
+    assert_eq!(request.method, Method::GET);
+    assert!(request
+        .url
+        .starts_with("https://api.test/health?sig_payload="));
+    assert!(request.body.is_none());
+}
+
+#[tokio::test]
+async fn test_custom_transport_errors_go_through_retry_policy() {
+    let transport = FakeTransport::default();
+    transport.fail(WusulError::Timeout).respond(
+        StatusCode::OK,
+        json!({"success": true, "data": {"status": "healthy"}}),
+    );
+
+    let health = client(&transport).health().await.unwrap();
+
+    assert_eq!(health["status"], "healthy");
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_custom_transport_error_responses_are_decoded() {
+    let transport = FakeTransport::default();
+    transport.respond(
+        StatusCode::NOT_FOUND,
+        json!({"success": false, "error": {"code": "ACCESS_PASS_NOT_FOUND", "message": "Access pass not found"}}),
+    );
+
+    let err = client(&transport)
+        .access_passes
+        .suspend("missing")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, WusulError::NotFound(_)));
+    assert_eq!(err.code(), Some("ACCESS_PASS_NOT_FOUND"));
+}
+
+#[tokio::test]
+async fn test_validation_error_field_errors() {
+    let transport = FakeTransport::default();
+    transport.respond(
+        StatusCode::BAD_REQUEST,
+        json!({
+            "success": false,
+            "error": {
+                "code": "VALIDATION_ERROR",
+                "message": "Request validation failed",
+                "details": [{"field": "full_name", "message": "Required"}]
+            }
+        }),
+    );
+
+    let params = IssueAccessPassParams {
+        card_template_id: "template_123".to_string(),
+        ..Default::default()
+    };
+    let err = client(&transport)
+        .access_passes
+        .issue(params)
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.error_code(), Some(&ErrorCode::ValidationError));
+    assert!(err.is_client_error());
+    assert!(!err.is_retryable());
+    assert_eq!(
+        err.field_errors(),
+        vec![FieldError {
+            path: "full_name".to_string(),
+            message: "Required".to_string(),
+        }]
+    );
+}
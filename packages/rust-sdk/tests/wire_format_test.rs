@@ -0,0 +1,230 @@
+//! Golden fixtures pinning the JSON wire format validated by the Wusul API.
+//!
+//! Request fixtures mirror the zod schemas in `apps/api/src/validators` and must
+//! round-trip byte-for-byte through the SDK types. Response fixtures mirror what
+//! the API services return inside the `data` field of the response envelope.
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::fmt::Debug;
+use wusul::types::{
+    AccessPass, AccessPassState, CardTemplate, Classification, CreateCardTemplateParams, Device,
+    EventLogEntry, EventType, EventTypeFilter, IssueAccessPassParams, ListAccessPassesParams,
+    Platform, Protocol, PublishStatus, ReadEventLogParams, UpdateAccessPassParams,
+    UpdateCardTemplateParams, UseCase,
+};
+
+fn fixture(name: &str) -> Value {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let contents = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    serde_json::from_str(&contents).unwrap()
+}
+
+/// Decode a fixture into `T`, re-encode it and check nothing was lost or renamed
+fn assert_round_trip<T>(name: &str) -> T
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let golden = fixture(name);
+    let decoded: T = serde_json::from_value(golden.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&decoded).unwrap(), golden, "{}", name);
+
+    let reparsed: T = serde_json::from_value(serde_json::to_value(&decoded).unwrap()).unwrap();
+    assert_eq!(reparsed, decoded, "{}", name);
+    decoded
+}
+
+fn items(name: &str) -> Value {
+    fixture(name)["items"].clone()
+}
+
+#[test]
+fn test_issue_access_pass_params_round_trip() {
+    let params: IssueAccessPassParams = assert_round_trip("issue_access_pass_params.json");
+
+    assert_eq!(params.card_template_id, "0xd7a8ab1c5e");
+    assert_eq!(params.classification, Some(Classification::FullTime));
+    assert_eq!(
+        params.tile_data.unwrap().check_in_url.as_deref(),
+        Some("https://hotel.example.com/check-in")
+    );
+}
+
+#[test]
+fn test_update_access_pass_params_round_trip() {
+    let params: UpdateAccessPassParams = assert_round_trip("update_access_pass_params.json");
+    assert_eq!(params.classification, Some(Classification::Contractor));
+}
+
+#[test]
+fn test_update_access_pass_params_omit_path_id() {
+    let params = UpdateAccessPassParams {
+        access_pass_id: "0x1a2b3c4d5e6f70".to_string(),
+        full_name: Some("Jane Doe-Smith".to_string()),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        serde_json::to_value(&params).unwrap(),
+        json!({"full_name": "Jane Doe-Smith"})
+    );
+}
+
+#[test]
+fn test_list_access_passes_params_round_trip() {
+    let params: ListAccessPassesParams = assert_round_trip("list_access_passes_params.json");
+    assert_eq!(params.state, Some(AccessPassState::Pending));
+}
+
+#[test]
+fn test_list_access_passes_params_builder() {
+    let params = ListAccessPassesParams::new("0xd7a8ab1c5e")
+        .with_state(AccessPassState::Pending)
+        .with_limit(25);
+    assert_eq!(
+        serde_json::to_value(&params).unwrap(),
+        fixture("list_access_passes_params.json")
+    );
+
+    // Only the fields listAccessPassesSchema accepts are sent
+    let params = ListAccessPassesParams::new("0xd7a8ab1c5e").with_page(3);
+    assert_eq!(
+        serde_json::to_value(&params).unwrap(),
+        json!({"template_id": "0xd7a8ab1c5e", "page": 3})
+    );
+}
+
+#[test]
+fn test_create_card_template_params_round_trip() {
+    let params: CreateCardTemplateParams = assert_round_trip("create_card_template_params.json");
+
+    assert_eq!(params.platform, Platform::Apple);
+    assert_eq!(params.use_case, UseCase::EmployeeBadge);
+    assert_eq!(params.protocol, Protocol::SmartTap);
+}
+
+#[test]
+fn test_update_card_template_params_round_trip() {
+    let params: UpdateCardTemplateParams = assert_round_trip("update_card_template_params.json");
+    assert!(params.card_template_id.is_empty());
+}
+
+#[test]
+fn test_read_event_log_params_round_trip() {
+    let params: ReadEventLogParams = assert_round_trip("read_event_log_params.json");
+
+    assert_eq!(params.device, Some(Device::Watch));
+    assert_eq!(params.event_type, Some(EventTypeFilter::Install));
+}
+
+#[test]
+fn test_enum_wire_values() {
+    let cases = [
+        (json!(Platform::Apple), "APPLE"),
+        (json!(Platform::Google), "GOOGLE"),
+        (json!(UseCase::EmployeeBadge), "EMPLOYEE_BADGE"),
+        (json!(UseCase::Residential), "RESIDENTIAL"),
+        (json!(UseCase::Vehicle), "VEHICLE"),
+        (json!(Protocol::Desfire), "DESFIRE"),
+        (json!(Protocol::Seos), "SEOS"),
+        (json!(Protocol::SmartTap), "SMART_TAP"),
+        (json!(AccessPassState::Pending), "PENDING"),
+        (json!(AccessPassState::Suspended), "SUSPENDED"),
+        (json!(PublishStatus::Published), "PUBLISHED"),
+        (json!(EventType::AccessPassIssued), "ACCESS_PASS_ISSUED"),
+    ];
+
+    for (value, expected) in cases {
+        assert_eq!(value, json!(expected));
+    }
+
+    let legacy: Result<Platform, _> = serde_json::from_value(json!("apple"));
+    assert!(legacy.is_err());
+}
+
+#[test]
+fn test_access_pass_issued_response() {
+    let pass: AccessPass = serde_json::from_value(fixture("access_pass_issued.json")).unwrap();
+
+    assert_eq!(pass.id, "0x1a2b3c4d5e6f70");
+    assert_eq!(pass.state, AccessPassState::Pending);
+    assert_eq!(
+        pass.install_url.as_deref(),
+        Some("https://wusul.com/install/0x1a2b3c4d5e6f70")
+    );
+    assert!(pass.created_at.is_some());
+
+    let encoded = serde_json::to_value(&pass).unwrap();
+    assert_eq!(
+        encoded["install_url"],
+        fixture("access_pass_issued.json")["install_url"]
+    );
+    assert_eq!(encoded["state"], "PENDING");
+}
+
+#[test]
+fn test_access_pass_list_response() {
+    let passes: Vec<AccessPass> = serde_json::from_value(items("access_pass_list.json")).unwrap();
+    let pass = &passes[0];
+
+    assert_eq!(pass.id, "0x1a2b3c4d5e6f70");
+    assert_eq!(pass.full_name.as_deref(), Some("Jane Smith"));
+    assert_eq!(pass.employee_id.as_deref(), Some("EMP002"));
+    assert_eq!(pass.state, AccessPassState::Active);
+    assert!(pass.expiration_date.is_some());
+
+    // Camel-case aliases are normalised to the snake_case wire format on output
+    let encoded = serde_json::to_value(pass).unwrap();
+    assert_eq!(encoded["full_name"], "Jane Smith");
+    assert!(encoded.get("fullName").is_none());
+}
+
+#[test]
+fn test_card_template_response() {
+    let template: CardTemplate = serde_json::from_value(fixture("card_template.json")).unwrap();
+
+    assert_eq!(template.platform, Some(Platform::Google));
+    assert_eq!(template.use_case, Some(UseCase::Hotel));
+    assert_eq!(template.protocol, Some(Protocol::Desfire));
+    assert_eq!(template.publish_status, Some(PublishStatus::Published));
+    assert_eq!(template.access_passes_count, Some(12));
+
+    let encoded = serde_json::to_value(&template).unwrap();
+    let reparsed: CardTemplate = serde_json::from_value(encoded.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&reparsed).unwrap(), encoded);
+    assert_eq!(
+        encoded["support_info"],
+        fixture("card_template.json")["support_info"]
+    );
+}
+
+#[test]
+fn test_event_log_response() {
+    let entries: Vec<EventLogEntry> = serde_json::from_value(items("event_log.json")).unwrap();
+
+    assert_eq!(entries[0].event_type, EventType::AccessPassActivated);
+    assert_eq!(entries[0].device, Some(Device::Watch));
+    assert_eq!(
+        entries[0]
+            .access_pass
+            .as_ref()
+            .unwrap()
+            .full_name
+            .as_deref(),
+        Some("Jane Smith")
+    );
+    assert_eq!(entries[1].event_type, EventType::CardTemplatePublished);
+    assert!(entries[1].access_pass.is_none());
+}
+
+#[test]
+fn test_unknown_event_type() {
+    let entry: EventLogEntry = serde_json::from_value(json!({
+        "id": "evt_1",
+        "event_type": "SOMETHING_NEW",
+        "created_at": "2024-01-01T00:00:00Z"
+    }))
+    .unwrap();
+
+    assert_eq!(entry.event_type, EventType::Unknown);
+}